@@ -0,0 +1,44 @@
+//! Errors returned when parsing or converting basic types.
+
+use std::{fmt, num::ParseIntError};
+
+use web3::types::U256;
+
+/// Error parsing or converting a basic type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BasicTypesError {
+    /// Value cannot be parsed as an integer. Contains the description of the underlying error.
+    Parse(String),
+    /// Value exceeds the maximum allowed for the type.
+    OutOfRange { value: u64, max: u64 },
+    /// Value doesn't fit into a 64-bit integer.
+    Overflow(U256),
+    /// Byte slice has an unexpected length.
+    InvalidLength { expected: usize, actual: usize },
+}
+
+impl From<ParseIntError> for BasicTypesError {
+    fn from(err: ParseIntError) -> Self {
+        Self::Parse(err.to_string())
+    }
+}
+
+impl fmt::Display for BasicTypesError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => write!(formatter, "failed to parse integer: {err}"),
+            Self::OutOfRange { value, max } => {
+                write!(formatter, "value {value} exceeds the maximum of {max}")
+            }
+            Self::Overflow(value) => write!(formatter, "value {value} doesn't fit into 64 bits"),
+            Self::InvalidLength { expected, actual } => {
+                write!(
+                    formatter,
+                    "invalid byte length: expected {expected} bytes, got {actual}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for BasicTypesError {}
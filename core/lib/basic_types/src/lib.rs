@@ -1,29 +1,53 @@
 //! The declaration of the most primitive types used in zkSync network.
 //!
 //! Most of them are just re-exported from the `web3` crate.
+//!
+//! # Binary encoding
+//!
+//! The binary `serde` encoding of the types declared in this crate is a part of its public contract, i.e.,
+//! it will not change between versions. Specifically:
+//!
+//! - Integer newtypes (e.g., [`MiniblockNumber`] or [`PriorityOpId`]) and [`L2ChainId`] are encoded
+//!   as the wrapped integer. With `bincode`, this means 4 or 8 little-endian bytes.
+//! - [`AccountTreeId`] is encoded as an [`Address`], which `web3` encodes as a `0x`-prefixed hex string
+//!   even for binary formats (i.e., 50 bytes with `bincode`). Use the [`compact_account_tree_id`] module
+//!   to encode an ID as 20 raw bytes in binary formats.
 
 use std::{
-    convert::{Infallible, TryFrom, TryInto},
+    cmp,
+    collections::HashSet,
+    convert::{TryFrom, TryInto},
     fmt,
-    num::ParseIntError,
-    ops::{Add, Deref, DerefMut, Sub},
+    iter::FromIterator,
+    ops::{Add, Deref, DerefMut, RangeInclusive, Sub},
     str::FromStr,
 };
 
-use serde::{de, Deserialize, Deserializer, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use web3::types::{BlockId, BlockNumber};
 pub use web3::{
     self, ethabi,
     types::{Address, Bytes, Log, TransactionRequest, H128, H160, H2048, H256, U128, U256, U64},
 };
 
+use crate::error::BasicTypesError;
+
 #[macro_use]
 mod macros;
 pub mod basic_fri_types;
+pub mod error;
 pub mod network;
+pub mod range;
+#[cfg(feature = "sqlx")]
+mod sqlx_types;
 
 /// Account place in the global state tree is uniquely identified by its address.
 /// Binary this type is represented by 160 bit big-endian representation of account address.
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Hash, Ord, PartialOrd)]
+///
+/// In human-readable formats, the ID is serialized as an EIP-55 checksummed address (see [`Self::to_human_address()`]).
+/// Deserialization accepts any `0x`-prefixed hex string of 20 bytes and, for backward compatibility,
+/// the legacy `{ "address": _ }` object. Binary formats use the same encoding as [`Address`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct AccountTreeId {
     address: Address,
 }
@@ -48,8 +72,233 @@ impl AccountTreeId {
         let address = Address::from_slice(&value);
         Self { address }
     }
+
+    /// Places the address into the 20 low bytes of a 32-byte word, e.g. to use it as a storage key.
+    /// This matches the layout of the `U256` conversion.
+    pub fn to_h256(&self) -> H256 {
+        let mut bytes = [0_u8; 32];
+        bytes[12..].copy_from_slice(self.address.as_bytes());
+        H256(bytes)
+    }
+
+    /// Creates an ID from a byte slice, checking that the slice has the address length (20 bytes).
+    pub fn try_from_slice(bytes: &[u8]) -> Result<Self, AccountTreeIdError> {
+        let bytes: [u8; 20] = bytes
+            .try_into()
+            .map_err(|_| AccountTreeIdError::InvalidLength(bytes.len()))?;
+        Ok(Self::from_fixed_bytes(bytes))
+    }
+
+    /// Encodes this ID as an [EIP-55] checksummed hex address, such as `0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed`.
+    /// The checksum is encoded in the case of hex digits, which makes the format resistant to typos.
+    ///
+    /// [EIP-55]: https://eips.ethereum.org/EIPS/eip-55
+    pub fn to_human_address(&self) -> String {
+        let hex_address = format!("{:x}", self.address);
+        let hash = web3::signing::keccak256(hex_address.as_bytes());
+
+        let mut output = String::with_capacity(2 + hex_address.len());
+        output.push_str("0x");
+        for (i, ch) in hex_address.chars().enumerate() {
+            let hash_nibble = if i % 2 == 0 {
+                hash[i / 2] >> 4
+            } else {
+                hash[i / 2] & 0xf
+            };
+            output.push(if hash_nibble >= 8 {
+                ch.to_ascii_uppercase()
+            } else {
+                ch
+            });
+        }
+        output
+    }
+
+    /// Parses an ID from the format produced by [`Self::to_human_address()`]. Unlike parsing an [`Address`],
+    /// this requires the checksum to be present and valid.
+    pub fn from_human_address(s: &str) -> Result<Self, AccountTreeIdError> {
+        let hex_address = s
+            .strip_prefix("0x")
+            .filter(|hex| hex.len() == 40 && hex.bytes().all(|byte| byte.is_ascii_hexdigit()))
+            .ok_or(AccountTreeIdError::InvalidHumanAddress)?;
+        let address: Address = hex_address
+            .parse()
+            .map_err(|_| AccountTreeIdError::InvalidHumanAddress)?;
+        let id = Self::new(address);
+        if id.to_human_address() != s {
+            return Err(AccountTreeIdError::InvalidChecksum);
+        }
+        Ok(id)
+    }
+
+    /// Checks whether this account belongs to the kernel space, i.e., its address is below `2^16`. Kernel space
+    /// contains system contracts (such as the bootloader at `0x8001`) and precompiles.
+    pub fn is_system_account(&self) -> bool {
+        self.address.as_bytes()[..18].iter().all(|&byte| byte == 0)
+    }
+
+    /// Checks whether two `U256` values map to the same ID. Since the conversion from `U256` only keeps
+    /// the 20 low bytes of the value, distinct values that differ only in high bytes collide.
+    pub fn would_collide(a: U256, b: U256) -> bool {
+        let mask = (U256::one() << 160) - 1;
+        (a & mask) == (b & mask)
+    }
+
+    /// Returns a deterministic shard index in `0..num_shards` for this account, e.g. for partitioned storage layouts.
+    /// The index is computed from the 8 top bytes of the address, so accounts sharing an address prefix
+    /// belong to the same shard.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_shards` is zero.
+    pub fn shard(&self, num_shards: u16) -> u16 {
+        assert!(num_shards > 0, "number of shards must be positive");
+        let mut prefix = [0_u8; 8];
+        prefix.copy_from_slice(&self.address.as_bytes()[..8]);
+        (u64::from_be_bytes(prefix) % u64::from(num_shards)) as u16
+    }
+
+    /// Checks whether this and `other` accounts share the top `bytes` bytes of their addresses. `bytes` is clamped
+    /// to the address length (20 bytes); an empty prefix is shared by all accounts.
+    pub fn shares_prefix(&self, other: &AccountTreeId, bytes: usize) -> bool {
+        let bytes = bytes.min(Address::len_bytes());
+        self.address.as_bytes()[..bytes] == other.address.as_bytes()[..bytes]
+    }
+}
+
+impl Serialize for AccountTreeId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_human_address())
+        } else {
+            self.address.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AccountTreeId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Address(Address),
+            Legacy { address: Address },
+        }
+
+        if deserializer.is_human_readable() {
+            let (Repr::Address(address) | Repr::Legacy { address }) =
+                Repr::deserialize(deserializer)?;
+            Ok(Self::new(address))
+        } else {
+            Address::deserialize(deserializer).map(Self::new)
+        }
+    }
+}
+
+/// [`AccountTreeId`] (de)serialized as an EIP-55 checksummed address (see [`AccountTreeId::to_human_address()`]).
+/// Unlike an [`Address`], deserialization rejects addresses without a valid checksum, which protects
+/// against typos, e.g. in config files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChecksummedAccountTreeId(pub AccountTreeId);
+
+impl Serialize for ChecksummedAccountTreeId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_human_address())
+    }
+}
+
+impl<'de> Deserialize<'de> for ChecksummedAccountTreeId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        AccountTreeId::from_human_address(&s)
+            .map(Self)
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Module to use with `#[serde(with = "checksummed_account_tree_id")]` to (de)serialize an [`AccountTreeId`] field
+/// as a checksummed address (see [`ChecksummedAccountTreeId`]). Unlike the default deserialization,
+/// addresses without a valid checksum are rejected.
+pub mod checksummed_account_tree_id {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{AccountTreeId, ChecksummedAccountTreeId};
+
+    pub fn serialize<S: Serializer>(id: &AccountTreeId, serializer: S) -> Result<S::Ok, S::Error> {
+        ChecksummedAccountTreeId(*id).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<AccountTreeId, D::Error> {
+        ChecksummedAccountTreeId::deserialize(deserializer).map(|id| id.0)
+    }
+}
+
+/// Compact (de)serialization for [`AccountTreeId`]s to use with `#[serde(with = "compact_account_tree_id")]`.
+/// For binary formats, the ID is encoded as 20 raw address bytes; human-readable formats use
+/// the same encoding as [`AccountTreeId`] itself.
+pub mod compact_account_tree_id {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{AccountTreeId, Address};
+
+    pub fn serialize<S: Serializer>(id: &AccountTreeId, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            id.serialize(serializer)
+        } else {
+            id.address().0.serialize(serializer)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<AccountTreeId, D::Error> {
+        if deserializer.is_human_readable() {
+            AccountTreeId::deserialize(deserializer)
+        } else {
+            let bytes = <[u8; 20]>::deserialize(deserializer)?;
+            Ok(AccountTreeId::new(Address::from(bytes)))
+        }
+    }
+}
+
+/// Error converting a value into an [`AccountTreeId`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountTreeIdError {
+    /// Provided bytes have an unexpected length.
+    InvalidLength(usize),
+    /// Human-readable address is not a `0x`-prefixed string of 40 hex digits.
+    InvalidHumanAddress,
+    /// Human-readable address has an invalid checksum.
+    InvalidChecksum,
+    /// 32-byte word has non-zero bytes outside the 20 low bytes occupied by the address.
+    NonZeroHighBytes,
+}
+
+impl fmt::Display for AccountTreeIdError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(len) => {
+                write!(
+                    formatter,
+                    "invalid account ID length: expected 20 bytes, got {len}"
+                )
+            }
+            Self::InvalidHumanAddress => formatter
+                .write_str("invalid human-readable address: expected 0x-prefixed 40 hex digits"),
+            Self::InvalidChecksum => {
+                formatter.write_str("invalid checksum of human-readable address")
+            }
+            Self::NonZeroHighBytes => {
+                formatter.write_str("32-byte word has non-zero bytes outside of the address")
+            }
+        }
+    }
 }
 
+impl std::error::Error for AccountTreeIdError {}
+
 impl Default for AccountTreeId {
     fn default() -> Self {
         Self {
@@ -58,6 +307,20 @@ impl Default for AccountTreeId {
     }
 }
 
+#[cfg(feature = "testing")]
+impl proptest::arbitrary::Arbitrary for AccountTreeId {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        use proptest::strategy::Strategy as _;
+
+        proptest::arbitrary::any::<[u8; 20]>()
+            .prop_map(Self::from_fixed_bytes)
+            .boxed()
+    }
+}
+
 #[allow(clippy::from_over_into)]
 impl Into<U256> for AccountTreeId {
     fn into(self) -> U256 {
@@ -67,13 +330,37 @@ impl Into<U256> for AccountTreeId {
     }
 }
 
+/// Truncates the value to its 20 low bytes.
 impl TryFrom<U256> for AccountTreeId {
-    type Error = Infallible;
+    type Error = AccountTreeIdError;
 
-    fn try_from(val: U256) -> Result<Self, Infallible> {
-        let mut be_data = vec![0; 32];
+    fn try_from(val: U256) -> Result<Self, Self::Error> {
+        let mut be_data = [0; 32];
         val.to_big_endian(&mut be_data);
-        Ok(Self::from_fixed_bytes(be_data[12..].try_into().unwrap()))
+        Self::try_from_slice(&be_data[12..])
+    }
+}
+
+/// Same as [`AccountTreeId::try_from_slice()`].
+impl TryFrom<&[u8]> for AccountTreeId {
+    type Error = AccountTreeIdError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::try_from_slice(bytes)
+    }
+}
+
+/// Inverse of [`AccountTreeId::to_h256()`]. Unlike the `U256` conversion, this checks that the 12 high bytes
+/// of the word are zero.
+impl TryFrom<H256> for AccountTreeId {
+    type Error = AccountTreeIdError;
+
+    fn try_from(word: H256) -> Result<Self, Self::Error> {
+        let (high_bytes, address_bytes) = word.as_bytes().split_at(12);
+        if high_bytes.iter().any(|&byte| byte != 0) {
+            return Err(AccountTreeIdError::NonZeroHighBytes);
+        }
+        Ok(Self::new(Address::from_slice(address_bytes)))
     }
 }
 
@@ -81,35 +368,66 @@ impl TryFrom<U256> for AccountTreeId {
 #[derive(Copy, Clone, Debug, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct L2ChainId(u64);
 
+/// For human-readable formats, an ID can be deserialized from an integer (which is what `Serialize` produces)
+/// or a decimal / hex string. For binary formats, the ID is deserialized from `u64`.
 impl<'de> Deserialize<'de> for L2ChainId {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let s: String = Deserialize::deserialize(deserializer)?;
-        s.parse().map_err(de::Error::custom)
+        struct L2ChainIdVisitor;
+
+        impl<'de> de::Visitor<'de> for L2ChainIdVisitor {
+            type Value = L2ChainId;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("L2 chain ID as an integer or a decimal / hex string")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                L2ChainId::from_u64_checked(value).map_err(E::custom)
+            }
+
+            // Some formats (e.g., TOML) represent all integers as signed.
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                let value = u64::try_from(value).map_err(|_| {
+                    E::invalid_value(de::Unexpected::Signed(value), &"non-negative integer")
+                })?;
+                self.visit_u64(value)
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(L2ChainIdVisitor)
+        } else {
+            let value = u64::deserialize(deserializer)?;
+            L2ChainId::from_u64_checked(value).map_err(de::Error::custom)
+        }
     }
 }
 
 impl FromStr for L2ChainId {
-    type Err = String;
+    type Err = BasicTypesError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // Parse the string as a U64
+        if s.is_empty() {
+            return Err(BasicTypesError::Parse("empty string".to_owned()));
+        }
+        // Parse the string as a U256 so that overflowing values are reported as such
         // try to parse as decimal first
-        let number = match U64::from_dec_str(s) {
+        let number = match U256::from_dec_str(s) {
             Ok(u) => u,
             Err(_) => {
                 // try to parse as hex
-                s.parse::<U64>()
-                    .map_err(|err| format!("Failed to parse L2ChainId: Err {err}"))?
+                s.parse::<U256>()
+                    .map_err(|err| BasicTypesError::Parse(err.to_string()))?
             }
         };
-
-        if number.as_u64() > L2ChainId::max().0 {
-            return Err(format!("Too big chain ID. MAX: {}", L2ChainId::max().0));
-        }
-        Ok(L2ChainId(number.as_u64()))
+        Self::new(number)
     }
 }
 
@@ -120,13 +438,132 @@ impl L2ChainId {
     // `v = 2*chainId + 36`, that should be save integer as well.
     const MAX: u64 = ((1 << 53) - 1 - 36) / 2;
 
-    pub fn max() -> Self {
+    pub const fn max() -> Self {
         Self(Self::MAX)
     }
 
-    pub fn as_u64(&self) -> u64 {
+    /// Creates a chain ID in `const` contexts, e.g. to define well-known chain IDs. Intended for values
+    /// known to be valid at compile time; use `TryFrom<u64>` for other values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value exceeds [`Self::max()`]. If evaluated in a `const` context, this is a compile-time error.
+    pub const fn from_u64_const(value: u64) -> Self {
+        assert!(
+            value <= Self::MAX,
+            "L2 chain ID exceeds the max allowed value"
+        );
+        Self(value)
+    }
+
+    /// Creates a chain ID from a `U256` value, e.g. one returned by an Ethereum node.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value exceeds [`Self::max()`].
+    pub fn new(value: U256) -> Result<Self, BasicTypesError> {
+        if value > U256::from(u64::MAX) {
+            return Err(BasicTypesError::Overflow(value));
+        }
+        Self::from_u64_checked(value.as_u64())
+    }
+
+    /// Single point of validation for all fallible constructors (`new()`, `TryFrom<u64>`, `FromStr`
+    /// and `Deserialize`).
+    fn from_u64_checked(value: u64) -> Result<Self, BasicTypesError> {
+        if value > Self::MAX {
+            return Err(BasicTypesError::OutOfRange {
+                value,
+                max: Self::MAX,
+            });
+        }
+        Ok(Self(value))
+    }
+
+    pub const fn as_u64(&self) -> u64 {
         self.0
     }
+
+    /// Returns the chain ID as a left-padded 32-byte big-endian word, i.e., its encoding as a `uint256`. This is
+    /// how the chain ID is encoded when deriving message domain separators (e.g., for EIP-712 messages).
+    pub fn domain_separator_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0_u8; 32];
+        bytes[24..].copy_from_slice(&self.0.to_be_bytes());
+        bytes
+    }
+
+    /// Parses a chain ID from an untrusted JSON value, which may be a non-negative integer or a decimal / hex string.
+    /// Unlike deserializing, this doesn't require a `Deserializer` and returns a typed error.
+    pub fn from_json_value(value: &serde_json::Value) -> Result<Self, BasicTypesError> {
+        match value {
+            serde_json::Value::Number(number) => {
+                let number = number.as_u64().ok_or_else(|| {
+                    BasicTypesError::Parse(format!("{number} is not a non-negative integer"))
+                })?;
+                Self::from_u64_checked(number)
+            }
+            serde_json::Value::String(s) => s.parse(),
+            _ => Err(BasicTypesError::Parse(format!(
+                "expected an integer or a string, got {value}"
+            ))),
+        }
+    }
+
+    /// Returns a compact label for the chain, such as `mainnet:324`, which can be used e.g. in dashboards.
+    /// The name is the [network name](network::L2Network::name()); for chains not known to this crate,
+    /// the label consists of the decimal chain ID only.
+    pub fn short_label(&self) -> String {
+        match network::L2Network::from(*self).name() {
+            Some(name) => format!("{name}:{}", self.0),
+            None => self.0.to_string(),
+        }
+    }
+
+    /// Returns a namespace for the chain that can be used to name resources in multi-tenant infrastructure,
+    /// such as `era-270`. The namespace is a valid DNS label and is unique for each chain ID; unlike
+    /// [`Self::short_label()`], it doesn't depend on whether the chain is known to this crate.
+    pub fn namespace(&self) -> String {
+        format!("era-{}", self.0)
+    }
+
+    /// Returns a visual identifier for the chain consisting of an emoji and an RGB color, which can be used
+    /// to consistently distinguish networks in UIs. The mapping is deterministic and stable across releases.
+    pub fn badge(&self) -> (char, [u8; 3]) {
+        const EMOJIS: [char; 8] = ['🔴', '🟠', '🟡', '🟢', '🔵', '🟣', '🟤', '⚫'];
+
+        // SplitMix64 finalizer; ensures that adjacent chain IDs get unrelated badges.
+        let mut hash = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        hash ^= hash >> 31;
+
+        let emoji = EMOJIS[(hash % EMOJIS.len() as u64) as usize];
+        let color = [(hash >> 8) as u8, (hash >> 16) as u8, (hash >> 24) as u8];
+        (emoji, color)
+    }
+
+    /// Checks whether this chain ID is contained in the `allowed` list. For large lists,
+    /// consider using [`L2ChainIdSet`] instead.
+    pub fn is_in(&self, allowed: &[L2ChainId]) -> bool {
+        allowed.contains(self)
+    }
+
+    /// Checks whether this chain belongs to the same [network family](network::NetworkFamily) as `other`.
+    /// For unknown chain IDs, the only family relation is equality.
+    pub fn same_family(&self, other: &L2ChainId) -> bool {
+        let family = network::NetworkFamily::for_l2_chain_id(*self);
+        let other_family = network::NetworkFamily::for_l2_chain_id(*other);
+        match (family, other_family) {
+            (Some(family), Some(other_family)) => family == other_family,
+            _ => self == other,
+        }
+    }
+}
+
+impl fmt::Display for L2ChainId {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}", self.0)
+    }
 }
 
 impl Default for L2ChainId {
@@ -136,17 +573,10 @@ impl Default for L2ChainId {
 }
 
 impl TryFrom<u64> for L2ChainId {
-    type Error = String;
+    type Error = BasicTypesError;
 
     fn try_from(val: u64) -> Result<Self, Self::Error> {
-        if val > L2ChainId::max().0 {
-            return Err(format!(
-                "Cannot convert given value {} into L2ChainId. It's greater than MAX: {},",
-                val,
-                L2ChainId::max().0,
-            ));
-        }
-        Ok(Self(val))
+        Self::from_u64_checked(val)
     }
 }
 
@@ -156,6 +586,127 @@ impl From<u32> for L2ChainId {
     }
 }
 
+/// Allows comparing chain IDs with integer literals, e.g. `chain_id == 324`.
+impl PartialEq<u64> for L2ChainId {
+    fn eq(&self, other: &u64) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<L2ChainId> for u64 {
+    fn eq(&self, other: &L2ChainId) -> bool {
+        *self == other.0
+    }
+}
+
+impl PartialOrd<u64> for L2ChainId {
+    fn partial_cmp(&self, other: &u64) -> Option<cmp::Ordering> {
+        self.0.partial_cmp(other)
+    }
+}
+
+impl PartialOrd<L2ChainId> for u64 {
+    fn partial_cmp(&self, other: &L2ChainId) -> Option<cmp::Ordering> {
+        self.partial_cmp(&other.0)
+    }
+}
+
+/// Set of L2 chain IDs with `O(1)` membership checks, e.g. an allowlist of chains served by a multi-tenant service.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct L2ChainIdSet(HashSet<L2ChainId>);
+
+impl L2ChainIdSet {
+    pub fn contains(&self, chain_id: L2ChainId) -> bool {
+        self.0.contains(&chain_id)
+    }
+
+    pub fn insert(&mut self, chain_id: L2ChainId) -> bool {
+        self.0.insert(chain_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<L2ChainId> for L2ChainIdSet {
+    fn from_iter<I: IntoIterator<Item = L2ChainId>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// `L2ChainId` is serialized as an integer, and is deserialized either from an integer or from a decimal / hex string,
+/// so its schema allows both.
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for L2ChainId {
+    fn schema_name() -> String {
+        "L2ChainId".to_owned()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        use schemars::schema::{InstanceType, SchemaObject};
+
+        SchemaObject {
+            instance_type: Some(vec![InstanceType::Integer, InstanceType::String].into()),
+            ..SchemaObject::default()
+        }
+        .into()
+    }
+}
+
+/// Generates chain IDs in the entire valid range, i.e., up to and including [`L2ChainId::max()`].
+#[cfg(feature = "testing")]
+impl proptest::arbitrary::Arbitrary for L2ChainId {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        use proptest::strategy::Strategy as _;
+
+        (0..=Self::MAX).prop_map(Self).boxed()
+    }
+}
+
+/// Serializes a value as a decimal string for human-readable formats (e.g., JSON), and uses the native
+/// value representation for binary formats. Useful for values that can exceed the JS safe integer range,
+/// such as [`PriorityOpId`].
+///
+/// Use this struct in annotation like that `#[serde(with = "SerializeAsDecimalString")]`.
+#[derive(Debug)]
+pub struct SerializeAsDecimalString;
+
+impl SerializeAsDecimalString {
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: fmt::Display + Serialize,
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(value)
+        } else {
+            value.serialize(serializer)
+        }
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr + Deserialize<'de>,
+        T::Err: fmt::Display,
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            s.parse().map_err(de::Error::custom)
+        } else {
+            T::deserialize(deserializer)
+        }
+    }
+}
+
 basic_type!(
     /// zkSync network block sequential index.
     MiniblockNumber,
@@ -182,6 +733,17 @@ basic_type!(
 
 basic_type!(
     /// Unique identifier of the priority operation in the zkSync network.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use zksync_basic_types::PriorityOpId;
+    /// let mut id = PriorityOpId(5);
+    /// assert_eq!(id.next(), PriorityOpId(6));
+    /// id.inc();
+    /// assert_eq!(id, PriorityOpId(6));
+    /// assert_eq!(PriorityOpId(u64::MAX).checked_next(), None);
+    /// ```
     PriorityOpId,
     u64
 );
@@ -192,6 +754,128 @@ basic_type!(
     u64
 );
 
+basic_type!(
+    /// Block timestamp as the number of seconds since the Unix epoch.
+    BlockTimestamp,
+    u64
+);
+
+impl MiniblockNumber {
+    /// Checks in debug builds that `current` immediately follows `prev`, or is the genesis miniblock
+    /// if `prev` is `None`. Returns `current` to allow chaining. In release builds, this is a no-op.
+    #[track_caller]
+    pub fn assert_monotonic(prev: Option<Self>, current: Self) -> Self {
+        let expected = prev.map_or(Self(0), Self::next);
+        debug_assert_eq!(
+            current, expected,
+            "miniblock numbers are not monotonic: {current} follows {prev:?}"
+        );
+        current
+    }
+
+    /// Checks whether all miniblocks in `range` are at or below `frontier` (e.g., the last migrated miniblock).
+    /// An empty range is always contained.
+    pub fn range_within(range: &RangeInclusive<Self>, frontier: Self) -> bool {
+        range.is_empty() || *range.end() <= frontier
+    }
+
+    /// Returns the number as a left-padded 32-byte big-endian word, i.e., the encoding of the number as a `U256`
+    /// that is used when hashing miniblocks. This is consistent with the `AccountTreeId` to `U256` conversion.
+    pub fn hash_preimage_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0_u8; 32];
+        bytes[28..].copy_from_slice(&self.get().to_be_bytes());
+        bytes
+    }
+}
+
+impl L1BatchNumber {
+    /// Returns the storage slot of the entry for this batch in a Solidity `mapping(uint256 => ...)` declared
+    /// at `mapping_slot`, i.e., `keccak256(abi.encode(batch_number, mapping_slot))`. This is how per-batch
+    /// commitments (e.g., stored batch hashes) are laid out in the storage of L1 contracts; the mapping slot
+    /// depends on the contract layout, so it's provided by the caller.
+    pub fn commitment_slot(&self, mapping_slot: U256) -> H256 {
+        let mut preimage = [0_u8; 64];
+        U256::from(self.get()).to_big_endian(&mut preimage[..32]);
+        mapping_slot.to_big_endian(&mut preimage[32..]);
+        H256(web3::signing::keccak256(&preimage))
+    }
+}
+
+impl Nonce {
+    /// Unpacks a full nonce stored by the `NonceHolder` system contract into the transaction (min) nonce
+    /// and the deployment nonce, in this order. The full nonce is `deployment_nonce * 2^128 + tx_nonce`.
+    /// Nonce values not fitting into `u32` are truncated.
+    pub fn from_packed(packed: U256) -> (Nonce, Nonce) {
+        let tx_nonce = Nonce(packed.low_u32());
+        let deployment_nonce = Nonce((packed >> 128).low_u32());
+        (tx_nonce, deployment_nonce)
+    }
+
+    /// Packs the transaction and deployment nonces into a full nonce. This is the inverse of [`Self::from_packed()`].
+    pub fn to_packed(tx_nonce: Nonce, deployment_nonce: Nonce) -> U256 {
+        (U256::from(deployment_nonce.get()) << 128) + U256::from(tx_nonce.get())
+    }
+}
+
+/// Packs an L1 batch number and a miniblock number into a single `U256` that can be used as a compact composite key.
+/// The L1 batch number occupies the most significant 32 bits, followed by the miniblock number in the next 32 bits;
+/// the remaining bits are zero. Hence, the numeric order of packed keys matches the lexicographic order of
+/// `(batch, miniblock)` pairs.
+pub fn pack_ids(batch: L1BatchNumber, miniblock: MiniblockNumber) -> U256 {
+    (U256::from(batch.get()) << 224) | (U256::from(miniblock.get()) << 192)
+}
+
+/// Unpacks an L1 batch number and a miniblock number packed with [`pack_ids()`]. The lower bits of `packed`
+/// not used by [`pack_ids()`] are ignored.
+pub fn unpack_ids(packed: U256) -> (L1BatchNumber, MiniblockNumber) {
+    let batch = L1BatchNumber((packed >> 224).low_u32());
+    let miniblock = MiniblockNumber((packed >> 192).low_u32());
+    (batch, miniblock)
+}
+
+#[cfg(feature = "chrono")]
+impl BlockTimestamp {
+    /// Converts this timestamp to a UTC date-time. Returns `None` if the timestamp is out of range.
+    pub fn to_date_time(self) -> Option<chrono::DateTime<chrono::Utc>> {
+        use chrono::TimeZone as _;
+
+        let secs = i64::try_from(self.get()).ok()?;
+        chrono::Utc.timestamp_opt(secs, 0).single()
+    }
+
+    /// Creates a timestamp from a UTC date-time, discarding its sub-second part. Returns `None`
+    /// for date-times before the Unix epoch.
+    pub fn from_date_time(date_time: chrono::DateTime<chrono::Utc>) -> Option<Self> {
+        u64::try_from(date_time.timestamp()).ok().map(Self)
+    }
+}
+
+impl L1BlockNumber {
+    /// Returns a block ID that can be used in `web3` queries to Ethereum.
+    pub fn to_block_id(self) -> BlockId {
+        BlockId::Number(self.into())
+    }
+}
+
+impl From<L1ChainId> for U64 {
+    fn from(chain_id: L1ChainId) -> Self {
+        U64::from(chain_id.get())
+    }
+}
+
+/// `U64` always fits into `L1ChainId`, so the conversion is infallible; `TryFrom<U64>` is provided by the blanket impl.
+impl From<U64> for L1ChainId {
+    fn from(chain_id: U64) -> Self {
+        Self(chain_id.as_u64())
+    }
+}
+
+impl From<L1BlockNumber> for BlockNumber {
+    fn from(number: L1BlockNumber) -> Self {
+        BlockNumber::Number(number.get().into())
+    }
+}
+
 #[allow(clippy::derivable_impls)]
 impl Default for MiniblockNumber {
     fn default() -> Self {
@@ -222,10 +906,37 @@ impl Default for PriorityOpId {
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use serde_json::from_str;
 
     use super::*;
 
+    proptest! {
+        #[test]
+        fn l2_chain_id_round_trip(value in 0..=L2ChainId::MAX) {
+            let chain_id = L2ChainId::try_from(value).unwrap();
+            prop_assert_eq!(L2ChainId::from_str(&chain_id.to_string()).unwrap(), chain_id);
+            prop_assert_eq!(L2ChainId::from_str(&format!("{value:#x}")).unwrap(), chain_id);
+
+            let json = serde_json::to_string(&chain_id).unwrap();
+            prop_assert_eq!(from_str::<L2ChainId>(&json).unwrap(), chain_id);
+            let json = serde_json::to_string(&chain_id.to_string()).unwrap();
+            prop_assert_eq!(from_str::<L2ChainId>(&json).unwrap(), chain_id);
+
+            let bytes = bincode::serialize(&chain_id).unwrap();
+            prop_assert_eq!(bincode::deserialize::<L2ChainId>(&bytes).unwrap(), chain_id);
+        }
+
+        #[test]
+        fn out_of_range_l2_chain_id_is_rejected(value in (L2ChainId::MAX + 1)..=u64::MAX) {
+            prop_assert!(L2ChainId::try_from(value).is_err());
+            prop_assert!(L2ChainId::from_str(&value.to_string()).is_err());
+            prop_assert!(L2ChainId::from_str(&format!("{value:#x}")).is_err());
+            prop_assert!(from_str::<L2ChainId>(&value.to_string()).is_err());
+            prop_assert!(from_str::<L2ChainId>(&format!("\"{value}\"")).is_err());
+        }
+    }
+
     #[test]
     fn test_from_str_valid_decimal() {
         let input = "42";
@@ -246,7 +957,10 @@ mod tests {
         let result = L2ChainId::from_str(input);
         assert_eq!(
             result,
-            Err(format!("Too big chain ID. MAX: {}", L2ChainId::max().0))
+            Err(BasicTypesError::OutOfRange {
+                value: u64::MAX,
+                max: L2ChainId::max().0
+            })
         );
     }
 
@@ -256,9 +970,64 @@ mod tests {
         let result = L2ChainId::from_str(input);
 
         assert!(result.is_err());
+        assert!(matches!(result, Err(BasicTypesError::Parse(_))));
         assert!(result
             .unwrap_err()
-            .contains("Failed to parse L2ChainId: Err "));
+            .to_string()
+            .contains("failed to parse integer: "));
+    }
+
+    #[test]
+    fn creating_l2_chain_id_from_u256() {
+        assert_eq!(L2ChainId::new(U256::from(324)).unwrap().as_u64(), 324);
+        assert_eq!(L2ChainId::new(L2ChainId::MAX.into()), Ok(L2ChainId::max()));
+        assert_eq!(
+            L2ChainId::new(U256::from(u64::MAX)),
+            Err(BasicTypesError::OutOfRange {
+                value: u64::MAX,
+                max: L2ChainId::MAX
+            })
+        );
+        let huge_value = U256::from(u64::MAX) + 1;
+        let err = L2ChainId::new(huge_value).unwrap_err();
+        assert_eq!(err, BasicTypesError::Overflow(huge_value));
+        assert_eq!(
+            err.to_string(),
+            "value 18446744073709551616 doesn't fit into 64 bits"
+        );
+
+        let err = L2ChainId::from_str("18446744073709551616").unwrap_err();
+        assert_eq!(err, BasicTypesError::Overflow(huge_value));
+        let err = L2ChainId::from_str("0x10000000000000000").unwrap_err();
+        assert_eq!(err, BasicTypesError::Overflow(huge_value));
+    }
+
+    #[test]
+    fn parsing_empty_l2_chain_id() {
+        let err = L2ChainId::from_str("").unwrap_err();
+        assert_eq!(err.to_string(), "failed to parse integer: empty string");
+        let err = from_str::<L2ChainId>("\"\"").unwrap_err().to_string();
+        assert!(err.contains("empty string"), "{err}");
+    }
+
+    #[test]
+    fn basic_types_error_variants() {
+        assert_eq!(
+            L2ChainId::try_from(L2ChainId::max().0 + 1),
+            Err(BasicTypesError::OutOfRange {
+                value: L2ChainId::max().0 + 1,
+                max: L2ChainId::max().0
+            })
+        );
+
+        let err = "-1".parse::<MiniblockNumber>().unwrap_err();
+        assert!(matches!(err, BasicTypesError::Parse(_)), "{err:?}");
+        let err = "4294967296".parse::<L1BatchNumber>().unwrap_err();
+        assert!(matches!(err, BasicTypesError::Parse(_)), "{err:?}");
+        assert_eq!(
+            err.to_string(),
+            "failed to parse integer: number too large to fit in target type"
+        );
     }
 
     #[test]
@@ -286,6 +1055,1072 @@ mod tests {
         assert!(result
             .unwrap_err()
             .to_string()
-            .contains("Failed to parse L2ChainId: Err Invalid character "));
+            .contains("failed to parse integer: Invalid character "));
+    }
+
+    #[test]
+    fn account_tree_id_conversions() {
+        let address = Address::repeat_byte(0x23);
+        let id = AccountTreeId::new(address);
+        let value: U256 = id.into();
+        assert_eq!(AccountTreeId::try_from(value), Ok(id));
+        assert_eq!(AccountTreeId::try_from_slice(address.as_bytes()), Ok(id));
+
+        assert_eq!(
+            AccountTreeId::try_from_slice(&[0; 19]),
+            Err(AccountTreeIdError::InvalidLength(19))
+        );
+        assert_eq!(
+            AccountTreeId::try_from_slice(&[0; 32]),
+            Err(AccountTreeIdError::InvalidLength(32))
+        );
+
+        assert_eq!(AccountTreeId::try_from(address.as_bytes()), Ok(id));
+        for len in [19, 21] {
+            let bytes = vec![0x23; len];
+            assert_eq!(
+                AccountTreeId::try_from(bytes.as_slice()),
+                Err(AccountTreeIdError::InvalidLength(len))
+            );
+        }
+    }
+
+    #[test]
+    fn const_constructors() {
+        const ERA_MAINNET: L2ChainId = L2ChainId::from_u64_const(324);
+        const GENESIS_MINIBLOCK: MiniblockNumber = MiniblockNumber::new(0);
+        const GENESIS_BATCH: L1BatchNumber = L1BatchNumber::new(0);
+
+        assert_eq!(ERA_MAINNET, L2ChainId::from(324));
+        assert_eq!(ERA_MAINNET.as_u64(), 324);
+        assert_eq!(GENESIS_MINIBLOCK, MiniblockNumber(0));
+        assert_eq!(GENESIS_BATCH, L1BatchNumber(0));
+        assert_eq!(L2ChainId::from_u64_const(L2ChainId::MAX), L2ChainId::max());
+    }
+
+    #[test]
+    #[should_panic(expected = "L2 chain ID exceeds the max allowed value")]
+    fn const_constructor_with_out_of_range_chain_id() {
+        L2ChainId::from_u64_const(L2ChainId::MAX + 1);
+    }
+
+    #[test]
+    fn account_tree_id_serialization() {
+        let address: Address = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+            .parse()
+            .unwrap();
+        let id = AccountTreeId::new(address);
+        let json = serde_json::to_value(id).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed")
+        );
+        assert_eq!(serde_json::from_value::<AccountTreeId>(json).unwrap(), id);
+        let lowercase_json = serde_json::json!(format!("{address:?}"));
+        assert_eq!(
+            serde_json::from_value::<AccountTreeId>(lowercase_json).unwrap(),
+            id
+        );
+        let legacy_json = serde_json::json!({ "address": address });
+        assert_eq!(
+            serde_json::from_value::<AccountTreeId>(legacy_json).unwrap(),
+            id
+        );
+        let short_json = serde_json::json!("0xabab");
+        serde_json::from_value::<AccountTreeId>(short_json).unwrap_err();
+        let long_json = serde_json::json!(format!("{:?}", H256::repeat_byte(0xab)));
+        serde_json::from_value::<AccountTreeId>(long_json).unwrap_err();
+
+        let bytes = bincode::serialize(&id).unwrap();
+        assert_eq!(bytes, bincode::serialize(&address).unwrap());
+        assert_eq!(bincode::deserialize::<AccountTreeId>(&bytes).unwrap(), id);
+    }
+
+    #[test]
+    fn account_tree_id_serialization_with_checksum() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Test {
+            #[serde(with = "checksummed_account_tree_id")]
+            account: AccountTreeId,
+        }
+
+        let address: Address = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+            .parse()
+            .unwrap();
+        let test = Test {
+            account: AccountTreeId::new(address),
+        };
+        let json = serde_json::to_value(&test).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "account": "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed" })
+        );
+        assert_eq!(serde_json::from_value::<Test>(json).unwrap(), test);
+
+        let bytes = bincode::serialize(&test).unwrap();
+        assert_eq!(bincode::deserialize::<Test>(&bytes).unwrap(), test);
+    }
+
+    #[test]
+    fn comparing_l2_chain_id_with_integers() {
+        let chain_id = L2ChainId::from(324);
+        assert!(chain_id == 324);
+        assert!(324 == chain_id);
+        assert!(chain_id != 270);
+        assert!(chain_id > 270 && chain_id < 325);
+        assert!(270 < chain_id && L2ChainId::max().as_u64() > chain_id);
+        assert!(chain_id <= 324 && chain_id >= 324);
+    }
+
+    #[test]
+    fn converting_numbers_from_u256() {
+        assert_eq!(
+            MiniblockNumber::try_from(U256::from(42)),
+            Ok(MiniblockNumber(42))
+        );
+        assert_eq!(
+            PriorityOpId::try_from(U256::from(u64::MAX)),
+            Ok(PriorityOpId(u64::MAX))
+        );
+        assert_eq!(
+            L1BatchNumber::try_from(U256::from(u64::from(u32::MAX) + 1)),
+            Err(BasicTypesError::OutOfRange {
+                value: u64::from(u32::MAX) + 1,
+                max: u32::MAX.into(),
+            })
+        );
+        let too_large = U256::from(u64::MAX) + 1;
+        assert_eq!(
+            Nonce::try_from(too_large),
+            Err(BasicTypesError::Overflow(too_large))
+        );
+    }
+
+    #[test]
+    fn converting_numbers_from_bytes() {
+        assert_eq!(
+            MiniblockNumber::try_from([1, 2, 3, 4].as_slice()),
+            Ok(MiniblockNumber(0x0102_0304))
+        );
+        let bytes: Vec<_> = PriorityOpId(12_345).be_bytes_iter().collect();
+        assert_eq!(
+            PriorityOpId::try_from(bytes.as_slice()),
+            Ok(PriorityOpId(12_345))
+        );
+        assert_eq!(
+            L1BatchNumber::try_from([1, 2, 3].as_slice()),
+            Err(BasicTypesError::InvalidLength {
+                expected: 4,
+                actual: 3,
+            })
+        );
+        let err = PriorityOpId::try_from([0; 4].as_slice()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid byte length: expected 8 bytes, got 4"
+        );
+    }
+
+    #[test]
+    fn converting_between_newtypes() {
+        let l1_batch_number = L1BatchNumber(42);
+        let miniblock_number = MiniblockNumber::from_raw(l1_batch_number.into_raw());
+        assert_eq!(miniblock_number, MiniblockNumber(42));
+        const GENESIS: MiniblockNumber = MiniblockNumber::from_raw(0);
+        assert_eq!(GENESIS.into_raw(), 0);
+    }
+
+    #[test]
+    fn offsetting_numbers() {
+        let mut number = MiniblockNumber(10);
+        assert_eq!(number + 5, MiniblockNumber(15));
+        assert_eq!(number - 10, MiniblockNumber(0));
+        number += 3;
+        assert_eq!(number, MiniblockNumber(13));
+        number -= 13;
+        assert_eq!(number, MiniblockNumber(0));
+        assert_eq!(PriorityOpId(u64::MAX - 1) + 1, PriorityOpId(u64::MAX));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "`MiniblockNumber` overflow: 4294967295 + 1")]
+    fn adding_to_number_with_overflow() {
+        let _ = MiniblockNumber(u32::MAX) + 1;
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "`L1BatchNumber` underflow: 2 - 3")]
+    fn subtracting_from_number_with_underflow() {
+        let mut number = L1BatchNumber(2);
+        number -= 3;
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "`MiniblockNumber` overflow: 4294967295 + 2")]
+    fn add_assigning_typed_number_with_overflow() {
+        let mut number = MiniblockNumber(u32::MAX);
+        number += MiniblockNumber(2);
+    }
+
+    #[test]
+    fn binary_layout_of_integer_types() {
+        fn assert_layout<T>(value: T, expected_bytes: &[u8])
+        where
+            T: fmt::Debug + PartialEq + Serialize + for<'de> Deserialize<'de>,
+        {
+            let bytes = bincode::serialize(&value).unwrap();
+            assert_eq!(bytes, expected_bytes, "{value:?}");
+            assert_eq!(bincode::deserialize::<T>(&bytes).unwrap(), value);
+        }
+
+        assert_layout(MiniblockNumber(0x0102_0304), &[4, 3, 2, 1]);
+        assert_layout(L1BatchNumber(0x0102_0304), &[4, 3, 2, 1]);
+        assert_layout(L1BlockNumber(0x0102_0304), &[4, 3, 2, 1]);
+        assert_layout(Nonce(0x0102_0304), &[4, 3, 2, 1]);
+        assert_layout(MiniblockNumber(u32::MAX), &[0xff; 4]);
+
+        let u64_bytes = [8, 7, 6, 5, 4, 3, 2, 1];
+        assert_layout(PriorityOpId(0x0102_0304_0506_0708), &u64_bytes);
+        assert_layout(L1ChainId(0x0102_0304_0506_0708), &u64_bytes);
+        assert_layout(BlockTimestamp(0x0102_0304_0506_0708), &u64_bytes);
+        assert_layout(L2ChainId::from(0x0102_0304), &[4, 3, 2, 1, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn binary_layout_of_account_tree_id() {
+        let id = AccountTreeId::new(Address::repeat_byte(0x12));
+        let bytes = bincode::serialize(&id).unwrap();
+        let mut expected_bytes = 42_u64.to_le_bytes().to_vec();
+        expected_bytes.extend_from_slice(format!("0x{}", "12".repeat(20)).as_bytes());
+        assert_eq!(bytes, expected_bytes);
+        assert_eq!(bincode::deserialize::<AccountTreeId>(&bytes).unwrap(), id);
+    }
+
+    #[test]
+    fn compact_account_tree_id_serialization() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Test {
+            #[serde(with = "compact_account_tree_id")]
+            account: AccountTreeId,
+        }
+
+        let test = Test {
+            account: AccountTreeId::new(Address::repeat_byte(0x12)),
+        };
+        let bytes = bincode::serialize(&test).unwrap();
+        assert_eq!(bytes, [0x12; 20]);
+        assert_eq!(bincode::deserialize::<Test>(&bytes).unwrap(), test);
+
+        let json = serde_json::to_value(&test).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "account": test.account.to_human_address() })
+        );
+        assert_eq!(serde_json::from_value::<Test>(json).unwrap(), test);
+    }
+
+    #[test]
+    fn account_tree_id_h256_conversions() {
+        let id = AccountTreeId::new(Address::repeat_byte(0x23));
+        let word = id.to_h256();
+        assert_eq!(word.as_bytes()[..12], [0; 12]);
+        assert_eq!(word.as_bytes()[12..], [0x23; 20]);
+        let value: U256 = id.into();
+        assert_eq!(U256::from_big_endian(word.as_bytes()), value);
+        assert_eq!(AccountTreeId::try_from(word), Ok(id));
+
+        let mut word = word;
+        word.0[11] = 1;
+        assert_eq!(
+            AccountTreeId::try_from(word),
+            Err(AccountTreeIdError::NonZeroHighBytes)
+        );
+        assert_eq!(
+            AccountTreeId::try_from(H256::repeat_byte(0xff)),
+            Err(AccountTreeIdError::NonZeroHighBytes)
+        );
+    }
+
+    #[test]
+    fn checking_system_accounts() {
+        for address in [0, 1, 0x8001, 0x800a, 0xffff] {
+            let id = AccountTreeId::new(Address::from_low_u64_be(address));
+            assert!(id.is_system_account(), "{id:?}");
+        }
+        for address in [0x1_0000, 0x1_8001, u64::MAX] {
+            let id = AccountTreeId::new(Address::from_low_u64_be(address));
+            assert!(!id.is_system_account(), "{id:?}");
+        }
+        assert!(!AccountTreeId::new(Address::repeat_byte(0x11)).is_system_account());
+    }
+
+    #[test]
+    fn serializing_priority_op_id_as_string() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Wrapper(#[serde(with = "SerializeAsDecimalString")] PriorityOpId);
+
+        let id = Wrapper(PriorityOpId((1 << 53) + 1));
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, "\"9007199254740993\"");
+        let restored: Wrapper = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, id);
+
+        let bytes = bincode::serialize(&id).unwrap();
+        assert_eq!(bytes, ((1_u64 << 53) + 1).to_le_bytes());
+        let restored: Wrapper = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(restored, id);
+    }
+
+    #[test]
+    fn asserting_monotonic_miniblock_numbers() {
+        let mut prev = None;
+        for number in 0..5 {
+            let current = MiniblockNumber::assert_monotonic(prev, MiniblockNumber(number));
+            assert_eq!(current, MiniblockNumber(number));
+            prev = Some(current);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "miniblock numbers are not monotonic")]
+    fn asserting_monotonic_miniblock_numbers_with_gap() {
+        MiniblockNumber::assert_monotonic(Some(MiniblockNumber(3)), MiniblockNumber(5));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "miniblock numbers are not monotonic")]
+    fn asserting_monotonic_miniblock_numbers_without_genesis() {
+        MiniblockNumber::assert_monotonic(None, MiniblockNumber(1));
+    }
+
+    #[test]
+    fn checking_miniblock_range_within_frontier() {
+        let frontier = MiniblockNumber(10);
+        let contained = MiniblockNumber(3)..=MiniblockNumber(7);
+        assert!(MiniblockNumber::range_within(&contained, frontier));
+        let touching = MiniblockNumber(3)..=MiniblockNumber(10);
+        assert!(MiniblockNumber::range_within(&touching, frontier));
+
+        let overlapping = MiniblockNumber(8)..=MiniblockNumber(12);
+        assert!(!MiniblockNumber::range_within(&overlapping, frontier));
+        let disjoint = MiniblockNumber(11)..=MiniblockNumber(20);
+        assert!(!MiniblockNumber::range_within(&disjoint, frontier));
+
+        let empty = MiniblockNumber(20)..=MiniblockNumber(15);
+        assert!(MiniblockNumber::range_within(&empty, frontier));
+    }
+
+    #[test]
+    fn miniblock_number_hash_preimage() {
+        let number = MiniblockNumber(0x0102_0304);
+        let mut expected = [0_u8; 32];
+        expected[28..].copy_from_slice(&[1, 2, 3, 4]);
+        assert_eq!(number.hash_preimage_bytes(), expected);
+
+        let mut u256_bytes = [0_u8; 32];
+        U256::from(number.0).to_big_endian(&mut u256_bytes);
+        assert_eq!(number.hash_preimage_bytes(), u256_bytes);
+    }
+
+    #[test]
+    fn formatting_basic_types_with_radix() {
+        let number = MiniblockNumber(255);
+        assert_eq!(number.to_string_radix(10), number.to_string());
+        assert_eq!(number.to_string_radix(16), "ff");
+        assert_eq!(number.to_string_radix(8), "377");
+        assert_eq!(number.to_string_radix(2), "11111111");
+        assert_eq!(MiniblockNumber(0).to_string_radix(16), "0");
+        assert_eq!(
+            PriorityOpId(u64::MAX).to_string_radix(16),
+            "ffffffffffffffff"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported radix: 36")]
+    fn formatting_basic_type_with_unsupported_radix() {
+        MiniblockNumber(1).to_string_radix(36);
+    }
+
+    #[test]
+    fn getting_wrapped_integer() {
+        assert_eq!(MiniblockNumber(7).get(), 7);
+        assert_eq!(L1BatchNumber(u32::MAX).get(), u32::MAX);
+        const NUMBER: u64 = PriorityOpId(3).get();
+        assert_eq!(NUMBER, 3);
+    }
+
+    #[test]
+    fn l2_chain_id_domain_separator_bytes() {
+        let mut expected = [0_u8; 32];
+        expected[30..].copy_from_slice(&[0x01, 0x44]);
+        assert_eq!(L2ChainId::from(324).domain_separator_bytes(), expected);
+
+        let mut u256_bytes = [0_u8; 32];
+        U256::from(L2ChainId::max().as_u64()).to_big_endian(&mut u256_bytes);
+        assert_eq!(L2ChainId::max().domain_separator_bytes(), u256_bytes);
+    }
+
+    #[test]
+    fn l2_chain_id_json_forms() {
+        let expected = L2ChainId::from(324);
+        for json in ["\"324\"", "\"0x144\"", "324"] {
+            let chain_id: L2ChainId = from_str(json).unwrap();
+            assert_eq!(chain_id, expected, "{json}");
+            let serialized = serde_json::to_string(&chain_id).unwrap();
+            assert_eq!(from_str::<L2ChainId>(&serialized).unwrap(), expected);
+        }
+
+        for json in [
+            "18446744073709551616",
+            "\"18446744073709551616\"",
+            "1e30",
+            "-324",
+        ] {
+            from_str::<L2ChainId>(json).unwrap_err();
+        }
+        let max = L2ChainId::max().as_u64();
+        let err = from_str::<L2ChainId>(&(max + 1).to_string())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("exceeds the maximum"), "{err}");
+    }
+
+    #[test]
+    fn l2_chain_id_from_json_value() {
+        use serde_json::json;
+
+        let expected = L2ChainId::from(324);
+        assert_eq!(L2ChainId::from_json_value(&json!(324)).unwrap(), expected);
+        assert_eq!(L2ChainId::from_json_value(&json!("324")).unwrap(), expected);
+        assert_eq!(
+            L2ChainId::from_json_value(&json!("0x144")).unwrap(),
+            expected
+        );
+        let max = L2ChainId::max().as_u64();
+        assert_eq!(
+            L2ChainId::from_json_value(&json!(max)).unwrap(),
+            L2ChainId::max()
+        );
+
+        let out_of_range = BasicTypesError::OutOfRange {
+            value: max + 1,
+            max,
+        };
+        let err = L2ChainId::from_json_value(&json!(max + 1)).unwrap_err();
+        assert_eq!(err, out_of_range);
+        let err = L2ChainId::from_json_value(&json!((max + 1).to_string())).unwrap_err();
+        assert_eq!(err, out_of_range);
+        let err = L2ChainId::from_json_value(&json!(u64::MAX)).unwrap_err();
+        assert!(matches!(err, BasicTypesError::OutOfRange { .. }), "{err}");
+
+        for invalid in [
+            json!(-1),
+            json!(1.5),
+            json!(true),
+            json!(null),
+            json!([324]),
+        ] {
+            let err = L2ChainId::from_json_value(&invalid).unwrap_err();
+            assert!(matches!(err, BasicTypesError::Parse(_)), "{err}");
+        }
+        let err = L2ChainId::from_json_value(&json!("what")).unwrap_err();
+        assert!(matches!(err, BasicTypesError::Parse(_)), "{err}");
+    }
+
+    #[test]
+    fn l2_chain_id_families() {
+        let goerli = L2ChainId::from(280);
+        let sepolia = L2ChainId::from(300);
+        assert!(goerli.same_family(&sepolia));
+        assert!(sepolia.same_family(&goerli));
+        assert!(goerli.same_family(&goerli));
+
+        let mainnet = L2ChainId::from(324);
+        let local = L2ChainId::from(270);
+        assert!(!mainnet.same_family(&goerli));
+        assert!(!local.same_family(&mainnet));
+
+        let unknown = L2ChainId::from(12_345);
+        assert!(unknown.same_family(&unknown));
+        assert!(!unknown.same_family(&L2ChainId::from(12_346)));
+        assert!(!unknown.same_family(&mainnet));
+        assert!(!mainnet.same_family(&unknown));
+    }
+
+    #[test]
+    fn l1_batch_commitment_slot() {
+        // `keccak256(abi.encode(1, 0))`, the well-known slot of `mapping[1]` for a mapping at slot 0.
+        let expected: H256 = "0xada5013122d395ba3c54772283fb069b10426056ef8ca54750cb9bb552a59e7d"
+            .parse()
+            .unwrap();
+        assert_eq!(L1BatchNumber(1).commitment_slot(U256::zero()), expected);
+
+        let expected: H256 = "0xc4250a0f26818bb2f4c50553605e6aa5374a022de55e294b0c5f6716bcaddbf8"
+            .parse()
+            .unwrap();
+        assert_eq!(L1BatchNumber(42).commitment_slot(7.into()), expected);
+        assert_ne!(L1BatchNumber(43).commitment_slot(7.into()), expected);
+    }
+
+    #[test]
+    fn packing_ids() {
+        let packed = pack_ids(L1BatchNumber(1), MiniblockNumber(2));
+        assert_eq!(packed, (U256::one() << 224) + (U256::from(2) << 192));
+        assert_eq!(unpack_ids(packed), (L1BatchNumber(1), MiniblockNumber(2)));
+
+        let ids = [(0, 0), (1, 0), (0, 1), (5, 42), (u32::MAX, u32::MAX)];
+        for (batch, miniblock) in ids {
+            let ids = (L1BatchNumber(batch), MiniblockNumber(miniblock));
+            assert_eq!(unpack_ids(pack_ids(ids.0, ids.1)), ids);
+        }
+
+        assert!(
+            pack_ids(L1BatchNumber(1), MiniblockNumber(0))
+                > pack_ids(L1BatchNumber(0), MiniblockNumber(u32::MAX))
+        );
+        assert_eq!(
+            unpack_ids(packed + U256::from(u64::MAX)),
+            (L1BatchNumber(1), MiniblockNumber(2))
+        );
+    }
+
+    #[test]
+    fn packing_nonces() {
+        let packed = Nonce::to_packed(Nonce(5), Nonce(1));
+        assert_eq!(packed, (U256::one() << 128) + 5);
+        assert_eq!(Nonce::from_packed(packed), (Nonce(5), Nonce(1)));
+
+        for (tx_nonce, deployment_nonce) in [(0, 0), (1, 0), (0, 1), (u32::MAX, u32::MAX)] {
+            let nonces = (Nonce(tx_nonce), Nonce(deployment_nonce));
+            let packed = Nonce::to_packed(nonces.0, nonces.1);
+            assert_eq!(Nonce::from_packed(packed), nonces);
+        }
+    }
+
+    #[test]
+    fn assign_operators() {
+        macro_rules! test_assign_operators {
+            ($($name:ident),+) => {
+                $(
+                    let mut number = $name(10);
+                    number += 5;
+                    assert_eq!(number, $name(15));
+                    number += $name(2);
+                    assert_eq!(number, $name(17));
+                    number -= 7;
+                    assert_eq!(number, $name(10));
+                    number -= $name(10);
+                    assert_eq!(number, $name(0));
+                )+
+            };
+        }
+
+        test_assign_operators!(
+            MiniblockNumber,
+            L1BatchNumber,
+            L1BlockNumber,
+            Nonce,
+            PriorityOpId,
+            L1ChainId
+        );
+    }
+
+    #[test]
+    fn basic_types_display_round_trip() {
+        macro_rules! test_round_trip {
+            ($($name:ident: $type:ty),+) => {
+                $(
+                    for value in [0, 1, 42, <$type>::MAX] {
+                        let number = $name(value);
+                        let displayed = number.to_string();
+                        assert_eq!(displayed, value.to_string());
+                        assert_eq!(displayed.parse::<$name>().unwrap(), number);
+                    }
+
+                    for input in ["", "-1", "0x10", " 1", "1.0"] {
+                        let err = input.parse::<$name>().unwrap_err();
+                        assert!(matches!(err, BasicTypesError::Parse(_)), "{err:?}");
+                    }
+                    let overflowing_input = (u128::from(<$type>::MAX) + 1).to_string();
+                    let err = overflowing_input.parse::<$name>().unwrap_err();
+                    assert!(matches!(err, BasicTypesError::Parse(_)), "{err:?}");
+                )+
+            };
+        }
+
+        test_round_trip!(
+            MiniblockNumber: u32,
+            L1BatchNumber: u32,
+            L1BlockNumber: u32,
+            Nonce: u32,
+            PriorityOpId: u64
+        );
+    }
+
+    #[test]
+    fn checked_and_saturating_arithmetic() {
+        macro_rules! test_arithmetic {
+            ($($name:ident: $type:ty),+) => {
+                $(
+                    let number = $name(10);
+                    assert_eq!(number.checked_add(5), Some($name(15)));
+                    assert_eq!(number.checked_sub(10), Some($name(0)));
+                    assert_eq!(number.checked_sub(11), None);
+                    assert_eq!($name(<$type>::MAX).checked_add(1), None);
+                    assert_eq!(number.saturating_add(5), $name(15));
+                    assert_eq!(number.saturating_sub(11), $name(0));
+                    assert_eq!($name(<$type>::MAX - 1).saturating_add(2), $name(<$type>::MAX));
+                )+
+            };
+        }
+
+        test_arithmetic!(
+            MiniblockNumber: u32,
+            L1BatchNumber: u32,
+            L1BlockNumber: u32,
+            Nonce: u32,
+            PriorityOpId: u64,
+            L1ChainId: u64
+        );
+    }
+
+    #[test]
+    fn iterating_over_number_ranges() {
+        let numbers: Vec<_> = MiniblockNumber(3).iter_to(MiniblockNumber(5)).collect();
+        assert_eq!(
+            numbers,
+            [MiniblockNumber(3), MiniblockNumber(4), MiniblockNumber(5)]
+        );
+        let numbers: Vec<_> = L1BatchNumber(3).iter_to(L1BatchNumber(3)).collect();
+        assert_eq!(numbers, [L1BatchNumber(3)]);
+        assert_eq!(L1BlockNumber(3).iter_to(L1BlockNumber(2)).count(), 0);
+
+        let numbers: Vec<_> = L1BatchNumber(u32::MAX - 1)
+            .iter_to(L1BatchNumber(u32::MAX))
+            .rev()
+            .collect();
+        assert_eq!(
+            numbers,
+            [L1BatchNumber(u32::MAX), L1BatchNumber(u32::MAX - 1)]
+        );
+    }
+
+    #[test]
+    fn incrementing_numbers() {
+        let mut number = MiniblockNumber(5);
+        assert_eq!(number.next(), MiniblockNumber(6));
+        number.inc();
+        assert_eq!(number, MiniblockNumber(6));
+        assert_eq!(number.checked_next(), Some(MiniblockNumber(7)));
+        assert_eq!(MiniblockNumber(u32::MAX).checked_next(), None);
+        assert_eq!(Nonce(u32::MAX - 1).next(), Nonce(u32::MAX));
+
+        assert_eq!(MiniblockNumber(5).as_u64(), 5);
+        assert_eq!(MiniblockNumber(5).as_usize(), 5);
+        assert_eq!(PriorityOpId(u64::MAX).as_u64(), u64::MAX);
+        let items = ["a", "b", "c"];
+        assert_eq!(items[L1BatchNumber(2).as_usize()], "c");
+    }
+
+    #[test]
+    #[should_panic(expected = "`Nonce` overflow")]
+    fn incrementing_number_with_overflow() {
+        let mut nonce = Nonce(u32::MAX);
+        nonce.inc();
+    }
+
+    #[test]
+    fn computing_distance_between_numbers() {
+        assert_eq!(MiniblockNumber(5).diff(MiniblockNumber(3)), 2);
+        assert_eq!(MiniblockNumber(3).diff(MiniblockNumber(5)), -2);
+        assert_eq!(MiniblockNumber(3).diff(MiniblockNumber(3)), 0);
+        assert_eq!(
+            L1BatchNumber(0).diff(L1BatchNumber(u32::MAX)),
+            -i128::from(u32::MAX)
+        );
+        assert_eq!(
+            PriorityOpId(0).diff(PriorityOpId(u64::MAX)),
+            -i128::from(u64::MAX)
+        );
+
+        assert_eq!(MiniblockNumber(3).abs_diff(MiniblockNumber(5)), 2);
+        assert_eq!(MiniblockNumber(5).abs_diff(MiniblockNumber(3)), 2);
+        assert_eq!(
+            L1BatchNumber(u32::MAX).abs_diff(L1BatchNumber(0)),
+            u64::from(u32::MAX)
+        );
+        assert_eq!(PriorityOpId(0).abs_diff(PriorityOpId(u64::MAX)), u64::MAX);
+    }
+
+    #[test]
+    fn computing_range_len() {
+        let range = MiniblockNumber(3)..=MiniblockNumber(5);
+        assert_eq!(MiniblockNumber::range_len(&range), 3);
+        let range = MiniblockNumber(3)..=MiniblockNumber(3);
+        assert_eq!(MiniblockNumber::range_len(&range), 1);
+        let range = MiniblockNumber(3)..=MiniblockNumber(2);
+        assert_eq!(MiniblockNumber::range_len(&range), 0);
+
+        let full_range = MiniblockNumber(0)..=MiniblockNumber(u32::MAX);
+        assert_eq!(MiniblockNumber::range_len(&full_range), 1 << 32);
+        let range = L1BatchNumber(u32::MAX)..=L1BatchNumber(u32::MAX);
+        assert_eq!(L1BatchNumber::range_len(&range), 1);
+        let full_range = PriorityOpId(0)..=PriorityOpId(u64::MAX);
+        assert_eq!(PriorityOpId::range_len(&full_range), u64::MAX);
+    }
+
+    #[test]
+    fn iterating_over_bytes() {
+        let number = MiniblockNumber(0x0102_0304);
+        assert_eq!(
+            number.le_bytes_iter().collect::<Vec<_>>(),
+            number.0.to_le_bytes()
+        );
+        assert_eq!(
+            number.be_bytes_iter().collect::<Vec<_>>(),
+            number.0.to_be_bytes()
+        );
+        assert_eq!(PriorityOpId(1).le_bytes_iter().len(), 8);
+    }
+
+    #[test]
+    fn account_tree_id_collisions() {
+        let value = U256::from(0x1234_5678_u64);
+        assert!(AccountTreeId::would_collide(value, value));
+        let value_with_high_bytes = value + (U256::one() << 160);
+        assert!(AccountTreeId::would_collide(value, value_with_high_bytes));
+        assert_eq!(
+            AccountTreeId::try_from(value),
+            AccountTreeId::try_from(value_with_high_bytes)
+        );
+        assert!(AccountTreeId::would_collide(
+            U256::MAX,
+            (U256::one() << 160) - 1
+        ));
+
+        assert!(!AccountTreeId::would_collide(value, value + 1));
+        assert!(!AccountTreeId::would_collide(
+            value,
+            value + (U256::one() << 159)
+        ));
+        assert!(!AccountTreeId::would_collide(U256::zero(), U256::MAX));
+    }
+
+    #[test]
+    fn sharding_account_tree_ids() {
+        let id = AccountTreeId::new(Address::repeat_byte(0xab));
+        assert_eq!(id.shard(1), 0);
+        assert_eq!(id.shard(16), id.shard(16));
+        assert_eq!(id.shard(u16::MAX), id.shard(u16::MAX));
+
+        for num_shards in [2_u16, 4, 16] {
+            let mut shard_sizes = vec![0_usize; usize::from(num_shards)];
+            for byte in 0..=u8::MAX {
+                let id = AccountTreeId::new(Address::repeat_byte(byte));
+                let shard = id.shard(num_shards);
+                assert!(shard < num_shards);
+                shard_sizes[usize::from(shard)] += 1;
+            }
+            let expected_size = 256 / usize::from(num_shards);
+            assert!(
+                shard_sizes.iter().all(|&size| size == expected_size),
+                "{shard_sizes:?}"
+            );
+        }
+
+        // Only the address prefix matters.
+        let mut address = Address::repeat_byte(0x12);
+        let shard = AccountTreeId::new(address).shard(10);
+        address.0[19] = 0xff;
+        assert_eq!(AccountTreeId::new(address).shard(10), shard);
+    }
+
+    #[test]
+    fn checking_account_tree_id_prefixes() {
+        let mut bytes = [0x11_u8; 20];
+        let id = AccountTreeId::from_fixed_bytes(bytes);
+        bytes[3] = 0x22;
+        let other_id = AccountTreeId::from_fixed_bytes(bytes);
+
+        assert!(id.shares_prefix(&other_id, 0));
+        assert!(id.shares_prefix(&other_id, 3));
+        assert!(other_id.shares_prefix(&id, 3));
+        assert!(!id.shares_prefix(&other_id, 4));
+        assert!(!id.shares_prefix(&other_id, 20));
+        assert!(!id.shares_prefix(&other_id, usize::MAX));
+
+        assert!(id.shares_prefix(&id, 20));
+        assert!(id.shares_prefix(&id, 100));
+        let zero_id = AccountTreeId::new(Address::zero());
+        assert!(!id.shares_prefix(&zero_id, 1));
+        assert!(zero_id.shares_prefix(&id, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "number of shards must be positive")]
+    fn sharding_account_tree_id_with_zero_shards() {
+        AccountTreeId::new(Address::zero()).shard(0);
+    }
+
+    #[test]
+    fn human_readable_account_tree_id() {
+        // Test vectors from EIP-55
+        let addresses = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+        for human_address in addresses {
+            let id = AccountTreeId::from_human_address(human_address).unwrap();
+            assert_eq!(*id.address(), human_address.parse::<Address>().unwrap());
+            assert_eq!(id.to_human_address(), human_address);
+        }
+
+        let id = AccountTreeId::new(Address::repeat_byte(0xab));
+        let human_address = id.to_human_address();
+        assert_eq!(AccountTreeId::from_human_address(&human_address), Ok(id));
+    }
+
+    #[test]
+    fn deserializing_checksummed_account_tree_id() {
+        let valid = "\"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed\"";
+        let id: ChecksummedAccountTreeId = from_str(valid).unwrap();
+        let lenient_address: Address = from_str(valid).unwrap();
+        assert_eq!(*id.0.address(), lenient_address);
+        assert_eq!(serde_json::to_string(&id).unwrap(), valid);
+
+        let invalid_checksum = "\"0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed\"";
+        let err = from_str::<ChecksummedAccountTreeId>(invalid_checksum).unwrap_err();
+        assert!(err.to_string().contains("invalid checksum"), "{err}");
+        let lenient_address: Address = from_str(invalid_checksum).unwrap();
+        assert_eq!(lenient_address, *id.0.address());
+
+        let lowercase = "\"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed\"";
+        let err = from_str::<ChecksummedAccountTreeId>(lowercase).unwrap_err();
+        assert!(err.to_string().contains("invalid checksum"), "{err}");
+        let lenient_address: Address = from_str(lowercase).unwrap();
+        assert_eq!(lenient_address, *id.0.address());
+    }
+
+    #[test]
+    fn rejecting_invalid_human_readable_account_tree_id() {
+        let corrupted = "0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        assert_eq!(
+            AccountTreeId::from_human_address(corrupted),
+            Err(AccountTreeIdError::InvalidChecksum)
+        );
+        let lowercase = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+        assert_eq!(
+            AccountTreeId::from_human_address(lowercase),
+            Err(AccountTreeIdError::InvalidChecksum)
+        );
+
+        let invalid_addresses = [
+            "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAedff",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeZ",
+        ];
+        for address in invalid_addresses {
+            assert_eq!(
+                AccountTreeId::from_human_address(address),
+                Err(AccountTreeIdError::InvalidHumanAddress)
+            );
+        }
+    }
+
+    #[test]
+    fn l2_chain_id_labels() {
+        let chain_id = L2ChainId::from(324);
+        assert_eq!(chain_id.to_string(), "324");
+        assert_eq!(chain_id.short_label(), "mainnet:324");
+        assert_eq!(L2ChainId::default().short_label(), "localhost:270");
+        for chain_id in [270, 280, 300, 324].map(L2ChainId::from) {
+            let label = chain_id.short_label();
+            let (name, _) = label.split_once(':').unwrap();
+            let network: network::L2Network = name.parse().unwrap();
+            assert_eq!(network.l2_chain_id(), chain_id);
+        }
+
+        let unknown_chain_id = L2ChainId::from(12_345);
+        assert_eq!(unknown_chain_id.to_string(), "12345");
+        assert_eq!(unknown_chain_id.short_label(), "12345");
+    }
+
+    #[test]
+    fn l2_chain_id_namespaces() {
+        assert_eq!(L2ChainId::default().namespace(), "era-270");
+        assert_eq!(L2ChainId::from(324).namespace(), "era-324");
+
+        for chain_id in [L2ChainId::from(0), L2ChainId::default(), L2ChainId::max()] {
+            let namespace = chain_id.namespace();
+            // Check that the namespace is a valid DNS label (RFC 1123).
+            assert!(namespace.len() <= 63, "{namespace}");
+            assert!(
+                namespace
+                    .bytes()
+                    .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'-'),
+                "{namespace}"
+            );
+            assert!(!namespace.starts_with('-') && !namespace.ends_with('-'));
+        }
+    }
+
+    #[test]
+    fn l2_chain_id_badges() {
+        // Badges must be stable, so we compare them with hard-coded values.
+        assert_eq!(L2ChainId::from(324).badge(), ('🔵', [35, 149, 57]));
+        assert_eq!(L2ChainId::default().badge(), ('🟤', [75, 253, 71]));
+        assert_eq!(L2ChainId::from(324).badge(), L2ChainId::from(324).badge());
+    }
+
+    #[test]
+    fn l2_chain_id_entry_points_share_validation() {
+        fn assert_same_result<E: fmt::Display>(
+            actual: Result<L2ChainId, E>,
+            expected: &Result<L2ChainId, BasicTypesError>,
+        ) {
+            match (actual, expected) {
+                (Ok(actual), Ok(expected)) => assert_eq!(actual, *expected),
+                (Err(err), Err(expected)) => {
+                    let err = err.to_string();
+                    assert!(err.contains(&expected.to_string()), "{err}");
+                }
+                (actual, expected) => panic!(
+                    "unexpected result: {:?}, expected {expected:?}",
+                    actual.map_err(|err| err.to_string())
+                ),
+            }
+        }
+
+        let values = [0, 1, 324, L2ChainId::MAX, L2ChainId::MAX + 1, u64::MAX];
+        for value in values {
+            let expected = L2ChainId::try_from(value);
+            assert_eq!(expected.is_ok(), value <= L2ChainId::MAX, "{value}");
+
+            assert_same_result(L2ChainId::from_str(&value.to_string()), &expected);
+            assert_same_result(L2ChainId::from_str(&format!("{value:#x}")), &expected);
+            assert_same_result(from_str::<L2ChainId>(&value.to_string()), &expected);
+            assert_same_result(from_str::<L2ChainId>(&format!("\"{value}\"")), &expected);
+            let bytes = bincode::serialize(&value).unwrap();
+            assert_same_result(bincode::deserialize::<L2ChainId>(&bytes), &expected);
+        }
+    }
+
+    #[test]
+    fn checking_l2_chain_id_membership() {
+        let allowed = [L2ChainId::from(270), L2ChainId::from(324)];
+        assert!(L2ChainId::from(324).is_in(&allowed));
+        assert!(!L2ChainId::from(300).is_in(&allowed));
+        assert!(!L2ChainId::from(324).is_in(&[]));
+
+        let mut set: L2ChainIdSet = allowed.iter().copied().collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(L2ChainId::from(270)));
+        assert!(!set.contains(L2ChainId::from(300)));
+        assert!(set.insert(L2ChainId::from(300)));
+        assert!(!set.insert(L2ChainId::from(300)));
+        assert!(set.contains(L2ChainId::from(300)));
+        assert!(L2ChainIdSet::default().is_empty());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn converting_block_timestamp_to_date_time() {
+        use chrono::TimeZone as _;
+
+        let timestamp = BlockTimestamp(1_700_000_000);
+        let date_time = timestamp.to_date_time().unwrap();
+        assert_eq!(date_time.to_rfc3339(), "2023-11-14T22:13:20+00:00");
+        assert_eq!(BlockTimestamp::from_date_time(date_time), Some(timestamp));
+
+        let epoch = chrono::Utc.timestamp_opt(0, 0).unwrap();
+        assert_eq!(BlockTimestamp(0).to_date_time(), Some(epoch));
+        assert_eq!(
+            BlockTimestamp::from_date_time(epoch),
+            Some(BlockTimestamp(0))
+        );
+
+        let with_millis = chrono::Utc.timestamp_millis_opt(1_700_000_000_500).unwrap();
+        assert_eq!(BlockTimestamp::from_date_time(with_millis), Some(timestamp));
+        let before_epoch = chrono::Utc.timestamp_opt(-1, 0).unwrap();
+        assert_eq!(BlockTimestamp::from_date_time(before_epoch), None);
+        assert_eq!(BlockTimestamp(u64::MAX).to_date_time(), None);
+    }
+
+    #[test]
+    fn converting_l1_chain_id() {
+        let chain_id = L1ChainId(5);
+        let web3_chain_id = U64::from(chain_id);
+        assert_eq!(web3_chain_id, U64::from(5));
+        assert_eq!(L1ChainId::from(web3_chain_id), chain_id);
+        assert_eq!(L1ChainId::try_from(web3_chain_id), Ok(chain_id));
+
+        let chain_id = L1ChainId(u64::MAX);
+        assert_eq!(L1ChainId::from(U64::from(chain_id)), chain_id);
+    }
+
+    #[test]
+    fn converting_l1_block_number() {
+        let number = L1BlockNumber(123);
+        assert_eq!(
+            BlockNumber::from(number),
+            BlockNumber::Number(U64::from(123))
+        );
+        assert_eq!(
+            number.to_block_id(),
+            BlockId::Number(BlockNumber::Number(U64::from(123)))
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    proptest! {
+        #[test]
+        fn arbitrary_l2_chain_id_round_trip(chain_id: L2ChainId) {
+            prop_assert!(chain_id <= L2ChainId::max());
+            let json = serde_json::to_string(&chain_id).unwrap();
+            prop_assert_eq!(from_str::<L2ChainId>(&json).unwrap(), chain_id);
+        }
+
+        #[test]
+        fn arbitrary_basic_types_round_trip(
+            account: AccountTreeId,
+            miniblock: MiniblockNumber,
+            priority_op_id: PriorityOpId,
+        ) {
+            prop_assert_eq!(AccountTreeId::from_fixed_bytes(account.to_fixed_bytes()), account);
+            let json = serde_json::to_string(&miniblock).unwrap();
+            prop_assert_eq!(from_str::<MiniblockNumber>(&json).unwrap(), miniblock);
+            let bytes = bincode::serialize(&priority_op_id).unwrap();
+            prop_assert_eq!(bincode::deserialize::<PriorityOpId>(&bytes).unwrap(), priority_op_id);
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn miniblock_number_json_schema() {
+        use schemars::schema::{InstanceType, SingleOrVec};
+
+        let schema = schemars::schema_for!(MiniblockNumber);
+        assert_eq!(
+            schema.schema.instance_type,
+            Some(SingleOrVec::Single(Box::new(InstanceType::Integer)))
+        );
+
+        let schema = schemars::schema_for!(L2ChainId);
+        assert_eq!(
+            schema.schema.instance_type,
+            Some(SingleOrVec::Vec(vec![
+                InstanceType::Integer,
+                InstanceType::String
+            ]))
+        );
     }
 }
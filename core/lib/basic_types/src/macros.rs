@@ -7,8 +7,150 @@ macro_rules! basic_type {
         pub struct $name(pub $type);
 
         impl $name {
+            /// Creates a value from the wrapped integer. Can be used in `const` contexts.
+            pub const fn new(value: $type) -> Self {
+                Self(value)
+            }
+
+            /// Returns the wrapped integer. Prefer this to accessing the tuple field directly.
+            pub const fn get(self) -> $type {
+                self.0
+            }
+
+            /// Creates a value from a raw integer extracted from another newtype using [`Self::into_raw()`].
+            /// Use this pair of methods instead of `Self(_)` / `.0` for conversions between newtypes
+            /// (e.g., `MiniblockNumber::from_raw(l1_batch_number.into_raw())`), so that such conversions
+            /// are easy to spot and are never accidental.
+            pub const fn from_raw(raw: $type) -> Self {
+                Self(raw)
+            }
+
+            /// Returns the raw wrapped integer to be converted to another newtype using `from_raw()`.
+            pub const fn into_raw(self) -> $type {
+                self.0
+            }
+
+            /// Returns the next value.
+            ///
+            /// # Panics
+            ///
+            /// Panics on overflow, both in debug and release builds.
+            #[track_caller]
             pub fn next(self) -> $name {
-                $name(self.0 + 1)
+                self.checked_next().unwrap_or_else(|| {
+                    panic!("`{}` overflow: {} has no next value", stringify!($name), self.0)
+                })
+            }
+
+            /// Returns the next value, or `None` on overflow.
+            pub fn checked_next(self) -> Option<$name> {
+                self.0.checked_add(1).map(Self)
+            }
+
+            /// Increments this value in place.
+            ///
+            /// # Panics
+            ///
+            /// Panics on overflow, same as [`Self::next()`].
+            #[track_caller]
+            pub fn inc(&mut self) {
+                *self = self.next();
+            }
+
+            /// Returns the wrapped integer widened to `u64`.
+            pub fn as_u64(self) -> u64 {
+                u64::from(self.0)
+            }
+
+            /// Returns the wrapped integer as `usize`, e.g. to index a `Vec`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value doesn't fit into `usize`, which can only happen on 32-bit targets for 64-bit types.
+            #[track_caller]
+            pub fn as_usize(self) -> usize {
+                <usize as std::convert::TryFrom<$type>>::try_from(self.0).unwrap_or_else(|_| {
+                    panic!("`{}` value {} doesn't fit into usize", stringify!($name), self.0)
+                })
+            }
+
+            /// Returns the signed distance `self - other`. Unlike the `Sub` operator, this never panics. The result
+            /// is widened to `i128` so that it can represent the distance for 64-bit types as well.
+            pub fn diff(self, other: Self) -> i128 {
+                i128::from(self.0) - i128::from(other.0)
+            }
+
+            /// Returns the absolute distance between `self` and `other`.
+            pub fn abs_diff(self, other: Self) -> u64 {
+                u64::from(self.0.abs_diff(other.0))
+            }
+
+            /// Returns the number of values in the inclusive `range`, or 0 if the range is empty. For 64-bit types,
+            /// the count saturates at `u64::MAX` for the range covering the entire domain.
+            pub fn range_len(range: &std::ops::RangeInclusive<Self>) -> u64 {
+                let (start, end) = (range.start().0, range.end().0);
+                if start > end {
+                    0
+                } else {
+                    u64::from(end - start).saturating_add(1)
+                }
+            }
+
+            /// Iterates over values from `self` to `end`, inclusive. Returns an empty iterator if `self > end`.
+            pub fn iter_to(self, end: Self) -> impl DoubleEndedIterator<Item = Self> {
+                (self.0..=end.0).map(Self)
+            }
+
+            /// Adds `rhs` to the wrapped integer, returning `None` on overflow.
+            pub fn checked_add(self, rhs: $type) -> Option<Self> {
+                self.0.checked_add(rhs).map(Self)
+            }
+
+            /// Subtracts `rhs` from the wrapped integer, returning `None` on underflow.
+            pub fn checked_sub(self, rhs: $type) -> Option<Self> {
+                self.0.checked_sub(rhs).map(Self)
+            }
+
+            /// Adds `rhs` to the wrapped integer, saturating at the maximum value.
+            pub fn saturating_add(self, rhs: $type) -> Self {
+                Self(self.0.saturating_add(rhs))
+            }
+
+            /// Subtracts `rhs` from the wrapped integer, saturating at zero.
+            pub fn saturating_sub(self, rhs: $type) -> Self {
+                Self(self.0.saturating_sub(rhs))
+            }
+
+            /// Formats the wrapped integer in the specified radix without a prefix, e.g. `ff` for 255 in radix 16.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `radix` is not one of 2, 8, 10 or 16.
+            pub fn to_string_radix(&self, radix: u32) -> String {
+                match radix {
+                    2 => format!("{:b}", self.0),
+                    8 => format!("{:o}", self.0),
+                    10 => self.0.to_string(),
+                    16 => format!("{:x}", self.0),
+                    _ => panic!("unsupported radix: {}", radix),
+                }
+            }
+
+            /// Iterates over bytes of the wrapped integer in the little-endian order.
+            pub fn le_bytes_iter(self) -> impl ExactSizeIterator<Item = u8> {
+                IntoIterator::into_iter(self.0.to_le_bytes())
+            }
+
+            /// Iterates over bytes of the wrapped integer in the big-endian order.
+            pub fn be_bytes_iter(self) -> impl ExactSizeIterator<Item = u8> {
+                IntoIterator::into_iter(self.0.to_be_bytes())
+            }
+        }
+
+        impl $crate::range::Step for $name {
+            fn forward_checked(start: Self, count: usize) -> Option<Self> {
+                let count = <$type as std::convert::TryFrom<usize>>::try_from(count).ok()?;
+                start.0.checked_add(count).map(Self)
             }
         }
 
@@ -27,7 +169,7 @@ macro_rules! basic_type {
         }
 
         impl FromStr for $name {
-            type Err = ParseIntError;
+            type Err = $crate::error::BasicTypesError;
 
             fn from_str(s: &str) -> Result<Self, Self::Err> {
                 let value = s.parse::<$type>()?;
@@ -35,37 +177,104 @@ macro_rules! basic_type {
             }
         }
 
+        impl std::convert::TryFrom<$crate::U256> for $name {
+            type Error = $crate::error::BasicTypesError;
+
+            fn try_from(value: $crate::U256) -> Result<Self, Self::Error> {
+                if value > $crate::U256::from(u64::MAX) {
+                    return Err($crate::error::BasicTypesError::Overflow(value));
+                }
+                let value = value.as_u64();
+                <$type as std::convert::TryFrom<u64>>::try_from(value)
+                    .map(Self)
+                    .map_err(|_| $crate::error::BasicTypesError::OutOfRange {
+                        value,
+                        max: <$type>::MAX.into(),
+                    })
+            }
+        }
+
+        /// Decodes the value from the big-endian representation of the wrapped integer.
+        impl std::convert::TryFrom<&[u8]> for $name {
+            type Error = $crate::error::BasicTypesError;
+
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                let bytes = <[u8; std::mem::size_of::<$type>()]>::try_from(bytes).map_err(|_| {
+                    $crate::error::BasicTypesError::InvalidLength {
+                        expected: std::mem::size_of::<$type>(),
+                        actual: bytes.len(),
+                    }
+                })?;
+                Ok(Self(<$type>::from_be_bytes(bytes)))
+            }
+        }
+
         impl fmt::Display for $name {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 write!(f, "{}", self.0)
             }
         }
 
+        // Binary operators intentionally only offset a value by a raw integer (`number + 1`, `number - 1`). There are
+        // no `Add<Self>` / `Sub<Self>` impls, since adding two absolute values (e.g., two miniblock numbers) makes
+        // no sense; use `diff()` / `abs_diff()` to get a distance between values. Assignment operators additionally
+        // accept a typed RHS (`number += Self(1)`). In debug builds, operators panic with a descriptive message
+        // on overflow.
         impl Add<$type> for $name {
             type Output = Self;
 
+            #[track_caller]
             fn add(self, other: $type) -> Self {
-                Self(self.0 + other)
+                if cfg!(debug_assertions) {
+                    self.checked_add(other).unwrap_or_else(|| {
+                        panic!("`{}` overflow: {} + {}", stringify!($name), self.0, other)
+                    })
+                } else {
+                    Self(self.0 + other)
+                }
             }
         }
 
         impl std::ops::AddAssign<$type> for $name {
+            #[track_caller]
             fn add_assign(&mut self, other: $type) {
-                self.0 += other;
+                *self = *self + other;
+            }
+        }
+
+        impl std::ops::AddAssign for $name {
+            #[track_caller]
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other.0;
             }
         }
 
         impl Sub<$type> for $name {
             type Output = Self;
 
+            #[track_caller]
             fn sub(self, other: $type) -> Self {
-                Self(self.0 - other)
+                if cfg!(debug_assertions) {
+                    self.checked_sub(other).unwrap_or_else(|| {
+                        panic!("`{}` underflow: {} - {}", stringify!($name), self.0, other)
+                    })
+                } else {
+                    Self(self.0 - other)
+                }
             }
         }
 
         impl std::ops::SubAssign<$type> for $name {
+            #[track_caller]
             fn sub_assign(&mut self, other: $type) {
-                self.0 -= other;
+                *self = *self - other;
+            }
+        }
+
+        impl std::ops::SubAssign for $name {
+            #[track_caller]
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other.0;
             }
         }
 
@@ -74,5 +283,28 @@ macro_rules! basic_type {
                 Self(value)
             }
         }
+
+        #[cfg(feature = "schemars")]
+        impl schemars::JsonSchema for $name {
+            fn schema_name() -> String {
+                stringify!($name).to_owned()
+            }
+
+            fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+                <$type as schemars::JsonSchema>::json_schema(gen)
+            }
+        }
+
+        #[cfg(feature = "testing")]
+        impl proptest::arbitrary::Arbitrary for $name {
+            type Parameters = ();
+            type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+            fn arbitrary_with((): ()) -> Self::Strategy {
+                use proptest::strategy::Strategy as _;
+
+                proptest::arbitrary::any::<$type>().prop_map(Self).boxed()
+            }
+        }
     };
 }
@@ -2,13 +2,13 @@
 //!
 
 // Built-in uses
-use std::{fmt, str::FromStr};
+use std::{convert::TryFrom, fmt, str::FromStr};
 
 // External uses
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
-use crate::L1ChainId;
+use crate::{L1ChainId, L2ChainId};
 
 // Local uses
 
@@ -34,6 +34,30 @@ pub enum Network {
     Test,
 }
 
+/// Family of networks that should be treated similarly, e.g. in routing. For example, a mainnet
+/// and its shadow fork belong to the same family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NetworkFamily {
+    /// Production networks.
+    Mainnet,
+    /// Public test networks.
+    Testnet,
+    /// Self-hosted networks used for development and testing.
+    Local,
+}
+
+impl NetworkFamily {
+    /// Returns the family of a well-known zkSync network, or `None` if the chain ID is unknown.
+    pub fn for_l2_chain_id(chain_id: L2ChainId) -> Option<Self> {
+        Some(match L2Network::from(chain_id) {
+            L2Network::Mainnet => Self::Mainnet,
+            L2Network::Goerli | L2Network::Sepolia => Self::Testnet,
+            L2Network::Localhost => Self::Local,
+            L2Network::Custom(_) => return None,
+        })
+    }
+}
+
 impl FromStr for Network {
     type Err = String;
 
@@ -67,6 +91,30 @@ impl fmt::Display for Network {
 }
 
 impl Network {
+    /// Returns all known networks, i.e., all networks except for [`Self::Unknown`] and [`Self::Test`].
+    /// Each returned network has a chain ID (see [`Self::chain_id()`]).
+    pub fn all() -> &'static [Network] {
+        &[
+            Self::Mainnet,
+            Self::Rinkeby,
+            Self::Ropsten,
+            Self::Goerli,
+            Self::Sepolia,
+            Self::Localhost,
+        ]
+    }
+
+    /// Returns the ID of the zkSync Era chain settling on this network, or `None` if there is no such chain.
+    pub fn l2_chain_id(self) -> Option<L2ChainId> {
+        Some(L2ChainId::from(match self {
+            Self::Mainnet => 324,
+            Self::Goerli => 280,
+            Self::Sepolia => 300,
+            Self::Localhost => 270,
+            Self::Rinkeby | Self::Ropsten | Self::Unknown | Self::Test => return None,
+        }))
+    }
+
     /// Returns the network chain ID on the Ethereum side.
     pub fn from_chain_id(chain_id: L1ChainId) -> Self {
         match *chain_id {
@@ -80,6 +128,18 @@ impl Network {
         }
     }
 
+    /// Returns the family of this network, or `None` if the network is unknown.
+    pub fn family(self) -> Option<NetworkFamily> {
+        match self {
+            Self::Mainnet => Some(NetworkFamily::Mainnet),
+            Self::Rinkeby | Self::Ropsten | Self::Goerli | Self::Sepolia => {
+                Some(NetworkFamily::Testnet)
+            }
+            Self::Localhost | Self::Test => Some(NetworkFamily::Local),
+            Self::Unknown => None,
+        }
+    }
+
     /// Returns the network chain ID on the Ethereum side.
     pub fn chain_id(self) -> L1ChainId {
         match self {
@@ -94,3 +154,213 @@ impl Network {
         }
     }
 }
+
+/// zkSync Era network: either one of the well-known environments, or a custom chain identified by its ID.
+/// Can be parsed from a network name (e.g., `mainnet`) or a decimal / hex L2 chain ID, which allows config loaders
+/// and CLIs to accept either uniformly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum L2Network {
+    /// zkSync Era mainnet settling on Ethereum Mainnet.
+    Mainnet,
+    /// zkSync Era testnet settling on Görli.
+    Goerli,
+    /// zkSync Era testnet settling on Sepolia.
+    Sepolia,
+    /// Self-hosted development network.
+    Localhost,
+    /// Network with a chain ID not matching any of the well-known networks.
+    Custom(L2ChainId),
+}
+
+impl L2Network {
+    const KNOWN: [Self; 4] = [Self::Mainnet, Self::Goerli, Self::Sepolia, Self::Localhost];
+
+    /// Returns the L1 network this network settles on, or `None` for custom networks.
+    pub fn l1_network(self) -> Option<Network> {
+        Some(match self {
+            Self::Mainnet => Network::Mainnet,
+            Self::Goerli => Network::Goerli,
+            Self::Sepolia => Network::Sepolia,
+            Self::Localhost => Network::Localhost,
+            Self::Custom(_) => return None,
+        })
+    }
+
+    /// Returns the chain ID of this network.
+    pub fn l2_chain_id(self) -> L2ChainId {
+        match self {
+            Self::Custom(chain_id) => chain_id,
+            known => known
+                .l1_network()
+                .and_then(Network::l2_chain_id)
+                .expect("well-known networks have L2 chain IDs"),
+        }
+    }
+
+    /// Returns the human-readable name of this network, or `None` for custom networks.
+    pub fn name(self) -> Option<&'static str> {
+        Some(match self {
+            Self::Mainnet => "mainnet",
+            Self::Goerli => "goerli",
+            Self::Sepolia => "sepolia",
+            Self::Localhost => "localhost",
+            Self::Custom(_) => return None,
+        })
+    }
+}
+
+/// Unknown chain IDs are mapped to [`L2Network::Custom`].
+impl From<L2ChainId> for L2Network {
+    fn from(chain_id: L2ChainId) -> Self {
+        Self::KNOWN
+            .iter()
+            .copied()
+            .find(|network| network.l2_chain_id() == chain_id)
+            .unwrap_or(Self::Custom(chain_id))
+    }
+}
+
+/// Fails if no well-known zkSync Era network settles on the L1 chain.
+impl TryFrom<L1ChainId> for L2Network {
+    type Error = L2NetworkError;
+
+    fn try_from(chain_id: L1ChainId) -> Result<Self, Self::Error> {
+        let l1_network = Network::from_chain_id(chain_id);
+        Self::KNOWN
+            .iter()
+            .copied()
+            .find(|network| network.l1_network() == Some(l1_network))
+            .ok_or(L2NetworkError::UnknownL1ChainId(chain_id))
+    }
+}
+
+/// Parses a network name, or a decimal / hex L2 chain ID. Unknown names are rejected.
+impl FromStr for L2Network {
+    type Err = L2NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(network) = Self::KNOWN
+            .iter()
+            .copied()
+            .find(|network| network.name() == Some(s))
+        {
+            return Ok(network);
+        }
+        if s.starts_with(|ch: char| ch.is_ascii_digit()) {
+            let chain_id = s
+                .parse::<L2ChainId>()
+                .map_err(|err| L2NetworkError::InvalidChainId(err.to_string()))?;
+            return Ok(chain_id.into());
+        }
+        Err(L2NetworkError::UnknownName(s.to_owned()))
+    }
+}
+
+/// Formats the network name for well-known networks, and the chain ID for custom ones, so that the output
+/// can be parsed back.
+impl fmt::Display for L2Network {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Custom(chain_id) => fmt::Display::fmt(chain_id, formatter),
+            known => formatter.write_str(known.name().unwrap()),
+        }
+    }
+}
+
+/// Error converting a value into an [`L2Network`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum L2NetworkError {
+    /// Network name is not known.
+    UnknownName(String),
+    /// Chain ID cannot be parsed. Contains the description of the underlying error.
+    InvalidChainId(String),
+    /// No well-known zkSync Era network settles on the L1 chain.
+    UnknownL1ChainId(L1ChainId),
+}
+
+impl fmt::Display for L2NetworkError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownName(name) => write!(formatter, "unknown network name: `{name}`"),
+            Self::InvalidChainId(err) => write!(formatter, "invalid L2 chain ID: {err}"),
+            Self::UnknownL1ChainId(chain_id) => write!(
+                formatter,
+                "no known zkSync Era network settles on L1 chain {}",
+                chain_id.0
+            ),
+        }
+    }
+}
+
+impl std::error::Error for L2NetworkError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_networks_have_names_and_chain_ids() {
+        for &network in Network::all() {
+            let name = network.to_string();
+            assert_ne!(name, "unknown");
+            assert_eq!(name.parse::<Network>(), Ok(network));
+            assert_eq!(Network::from_chain_id(network.chain_id()), network);
+        }
+    }
+
+    #[test]
+    fn parsing_l2_networks() {
+        for network in L2Network::KNOWN {
+            let name = network.to_string();
+            assert_eq!(name.parse::<L2Network>(), Ok(network));
+            assert_eq!(L2Network::from(network.l2_chain_id()), network);
+            let l1_network = network.l1_network().unwrap();
+            assert_eq!(L2Network::try_from(l1_network.chain_id()), Ok(network));
+        }
+
+        assert_eq!("324".parse::<L2Network>(), Ok(L2Network::Mainnet));
+        assert_eq!("0x12c".parse::<L2Network>(), Ok(L2Network::Sepolia));
+        let custom = L2Network::Custom(L2ChainId::from(123));
+        assert_eq!("123".parse::<L2Network>(), Ok(custom));
+        assert_eq!(custom.to_string().parse::<L2Network>(), Ok(custom));
+        assert_eq!(custom.l2_chain_id(), L2ChainId::from(123));
+        assert_eq!(custom.name(), None);
+        assert_eq!(custom.l1_network(), None);
+
+        assert_eq!(
+            "rinkeby".parse::<L2Network>(),
+            Err(L2NetworkError::UnknownName("rinkeby".to_owned()))
+        );
+        assert!(matches!(
+            "12z".parse::<L2Network>(),
+            Err(L2NetworkError::InvalidChainId(_))
+        ));
+        assert_eq!(
+            L2Network::try_from(L1ChainId(4)),
+            Err(L2NetworkError::UnknownL1ChainId(L1ChainId(4)))
+        );
+    }
+
+    #[test]
+    fn building_network_picker() {
+        let options: Vec<_> = Network::all()
+            .iter()
+            .filter_map(|&network| Some((network.to_string(), network.l2_chain_id()?.as_u64())))
+            .collect();
+        assert_eq!(
+            options,
+            [
+                ("mainnet".to_owned(), 324),
+                ("goerli".to_owned(), 280),
+                ("sepolia".to_owned(), 300),
+                ("localhost".to_owned(), 270),
+            ]
+        );
+        for &network in Network::all() {
+            if let Some(chain_id) = network.l2_chain_id() {
+                let family = NetworkFamily::for_l2_chain_id(chain_id);
+                assert_eq!(family, network.family(), "{network}");
+            }
+        }
+    }
+}
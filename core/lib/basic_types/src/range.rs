@@ -0,0 +1,103 @@
+//! Utilities for working with ranges of basic types, such as miniblock or L1 batch numbers.
+
+use std::{iter, ops::RangeInclusive};
+
+/// Types that have a notion of successor. This is a stable analogue of `std::iter::Step` implemented
+/// for all integer newtypes declared in this crate.
+pub trait Step: Copy + Ord {
+    /// Returns the value obtained by stepping `count` times forward from `start`, or `None` on overflow.
+    fn forward_checked(start: Self, count: usize) -> Option<Self>;
+}
+
+/// Splits `range` into consecutive subranges with at most `chunk_size` values each. Returns an empty iterator
+/// for an empty `range`.
+///
+/// # Panics
+///
+/// Panics if `chunk_size` is zero.
+pub fn chunked_ranges<T: Step>(
+    range: RangeInclusive<T>,
+    chunk_size: usize,
+) -> impl Iterator<Item = RangeInclusive<T>> {
+    assert!(chunk_size > 0, "chunk size must be positive");
+
+    let (start, end) = range.into_inner();
+    let mut next_start = Some(start).filter(|&start| start <= end);
+    iter::from_fn(move || {
+        let chunk_start = next_start?;
+        let chunk_end = T::forward_checked(chunk_start, chunk_size - 1)
+            .map_or(end, |chunk_end| chunk_end.min(end));
+        next_start = if chunk_end < end {
+            T::forward_checked(chunk_end, 1)
+        } else {
+            None
+        };
+        Some(chunk_start..=chunk_end)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{L1BatchNumber, MiniblockNumber};
+
+    #[test]
+    fn chunking_miniblock_ranges() {
+        let range = MiniblockNumber(0)..=MiniblockNumber(6);
+        let chunks: Vec<_> = chunked_ranges(range.clone(), 3).collect();
+        assert_eq!(
+            chunks,
+            [
+                MiniblockNumber(0)..=MiniblockNumber(2),
+                MiniblockNumber(3)..=MiniblockNumber(5),
+                MiniblockNumber(6)..=MiniblockNumber(6),
+            ]
+        );
+
+        let chunks: Vec<_> = chunked_ranges(range.clone(), 7).collect();
+        assert_eq!(chunks, [range.clone()]);
+        let chunks: Vec<_> = chunked_ranges(range, 100).collect();
+        assert_eq!(chunks, [MiniblockNumber(0)..=MiniblockNumber(6)]);
+
+        let range = MiniblockNumber(5)..=MiniblockNumber(4);
+        assert_eq!(chunked_ranges(range, 3).count(), 0);
+    }
+
+    #[test]
+    fn chunking_l1_batch_ranges() {
+        let range = L1BatchNumber(10)..=L1BatchNumber(13);
+        let chunks: Vec<_> = chunked_ranges(range, 1).collect();
+        assert_eq!(
+            chunks,
+            [
+                L1BatchNumber(10)..=L1BatchNumber(10),
+                L1BatchNumber(11)..=L1BatchNumber(11),
+                L1BatchNumber(12)..=L1BatchNumber(12),
+                L1BatchNumber(13)..=L1BatchNumber(13),
+            ]
+        );
+    }
+
+    #[test]
+    fn chunking_range_near_max_value() {
+        let range = L1BatchNumber(u32::MAX - 4)..=L1BatchNumber(u32::MAX);
+        let chunks: Vec<_> = chunked_ranges(range, 3).collect();
+        assert_eq!(
+            chunks,
+            [
+                L1BatchNumber(u32::MAX - 4)..=L1BatchNumber(u32::MAX - 2),
+                L1BatchNumber(u32::MAX - 1)..=L1BatchNumber(u32::MAX),
+            ]
+        );
+
+        let range = L1BatchNumber(0)..=L1BatchNumber(u32::MAX);
+        let chunks: Vec<_> = chunked_ranges(range, usize::MAX).collect();
+        assert_eq!(chunks, [L1BatchNumber(0)..=L1BatchNumber(u32::MAX)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be positive")]
+    fn chunking_with_zero_chunk_size() {
+        let _ = chunked_ranges(MiniblockNumber(0)..=MiniblockNumber(1), 0);
+    }
+}
@@ -0,0 +1,51 @@
+//! `sqlx` encoding / decoding for numeric basic types, so that they can be bound to queries
+//! and read from rows without manual casts.
+//!
+//! Types wrapping `u32` are stored as Postgres `BIGINT`s, which is how block numbers are stored in the DB.
+//! Decoding fails if the stored value doesn't fit into `u32`. Types wrapping `u64` are not supported,
+//! since not all their values can be represented as `BIGINT`s, and encoding cannot fail.
+
+use std::convert::TryFrom;
+
+use sqlx::{
+    encode::IsNull,
+    error::BoxDynError,
+    postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef},
+    Decode, Encode, Postgres, Type,
+};
+
+use crate::{L1BatchNumber, L1BlockNumber, MiniblockNumber, Nonce};
+
+macro_rules! impl_sqlx_for_u32_type {
+    ($($name:ident),+) => {
+        $(
+        impl Type<Postgres> for $name {
+            fn type_info() -> PgTypeInfo {
+                <i64 as Type<Postgres>>::type_info()
+            }
+
+            fn compatible(ty: &PgTypeInfo) -> bool {
+                <i64 as Type<Postgres>>::compatible(ty)
+            }
+        }
+
+        impl<'q> Encode<'q, Postgres> for $name {
+            fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+                <i64 as Encode<'q, Postgres>>::encode_by_ref(&i64::from(self.get()), buf)
+            }
+        }
+
+        impl<'r> Decode<'r, Postgres> for $name {
+            fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+                let value = <i64 as Decode<'r, Postgres>>::decode(value)?;
+                let value = u32::try_from(value).map_err(|_| {
+                    format!("value {value} is out of range for `{}`", stringify!($name))
+                })?;
+                Ok(Self(value))
+            }
+        }
+        )+
+    };
+}
+
+impl_sqlx_for_u32_type!(MiniblockNumber, L1BatchNumber, L1BlockNumber, Nonce);
@@ -7,6 +7,7 @@ use std::{
 use anyhow::Context as _;
 use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
 use sqlx::Row;
+use thiserror::Error;
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
     block::{BlockGasCount, L1BatchHeader, MiniblockHeader},
@@ -22,6 +23,16 @@ use crate::{
     StorageProcessor,
 };
 
+/// Error returned by [`BlocksDal::validate_batch_exists()`] if the requested L1 batch is not sealed yet.
+#[derive(Debug, Clone, PartialEq, Error)]
+#[error("L1 batch #{requested} is not sealed; last sealed L1 batch: {last_sealed:?}")]
+pub struct BatchNotFound {
+    /// Requested L1 batch number.
+    pub requested: L1BatchNumber,
+    /// Number of the last sealed L1 batch, or `None` if there are no L1 batches in the DB.
+    pub last_sealed: Option<L1BatchNumber>,
+}
+
 #[derive(Debug)]
 pub struct BlocksDal<'a, 'c> {
     pub(crate) storage: &'a mut StorageProcessor<'c>,
@@ -60,6 +71,28 @@ impl BlocksDal<'_, '_> {
         Ok(row.number.map(|num| L1BatchNumber(num as u32)))
     }
 
+    /// Checks whether the specified L1 batch is sealed, i.e., doesn't exceed the last sealed L1 batch.
+    pub async fn is_batch_sealed(&mut self, batch: L1BatchNumber) -> sqlx::Result<bool> {
+        Ok(self.validate_batch_exists(batch).await?.is_ok())
+    }
+
+    /// Checks that the specified L1 batch is sealed, so that queries for future L1 batches can be rejected early
+    /// instead of returning empty results. The outer error is a DB error.
+    pub async fn validate_batch_exists(
+        &mut self,
+        batch: L1BatchNumber,
+    ) -> sqlx::Result<Result<(), BatchNotFound>> {
+        let last_sealed = self.get_sealed_l1_batch_number().await?;
+        Ok(if last_sealed.map_or(false, |last| batch <= last) {
+            Ok(())
+        } else {
+            Err(BatchNotFound {
+                requested: batch,
+                last_sealed,
+            })
+        })
+    }
+
     pub async fn get_sealed_miniblock_number(&mut self) -> sqlx::Result<Option<MiniblockNumber>> {
         let row = sqlx::query!(
             r#"
@@ -1920,6 +1953,72 @@ impl BlocksDal<'_, '_> {
         )))
     }
 
+    /// Returns the number of the L1 batch that includes the specified miniblock, or `None` if the miniblock
+    /// is not included into a sealed L1 batch yet (or doesn't exist).
+    pub async fn get_batch_for_miniblock(
+        &mut self,
+        miniblock_number: MiniblockNumber,
+    ) -> sqlx::Result<Option<L1BatchNumber>> {
+        let number: Option<i64> = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number
+            FROM
+                miniblocks
+            WHERE
+                number = $1
+            "#,
+            miniblock_number.0 as i64
+        )
+        .fetch_optional(self.storage.conn())
+        .await?
+        .and_then(|row| row.l1_batch_number);
+
+        Ok(number.map(|number| L1BatchNumber(number as u32)))
+    }
+
+    /// Steps forward from the specified miniblock by an L1 batch, i.e., returns the first miniblock of the L1 batch
+    /// following the batch that includes `miniblock`. This allows walking the chain batch by batch in miniblock space.
+    /// Returns `None` if `miniblock` is not included into a sealed L1 batch, or if the next L1 batch is not sealed
+    /// (i.e., at the chain tip).
+    pub async fn advance_by_batch(
+        &mut self,
+        miniblock: MiniblockNumber,
+    ) -> sqlx::Result<Option<MiniblockNumber>> {
+        let Some(l1_batch_number) = self.get_batch_for_miniblock(miniblock).await? else {
+            return Ok(None);
+        };
+        let Some(next_l1_batch_number) = l1_batch_number.checked_add(1) else {
+            return Ok(None);
+        };
+        let range = self
+            .get_miniblock_range_of_l1_batch(next_l1_batch_number)
+            .await?;
+        Ok(range.map(|(first_miniblock, _)| first_miniblock))
+    }
+
+    /// Returns the number of miniblocks in the specified L1 batch, or 0 if the batch is not sealed.
+    pub async fn miniblock_count_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> sqlx::Result<u32> {
+        let count = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                miniblocks
+            WHERE
+                l1_batch_number = $1
+            "#,
+            l1_batch_number.0 as i64
+        )
+        .fetch_one(self.storage.conn())
+        .await?
+        .count;
+        Ok(count as u32)
+    }
+
     /// Returns `true` if there exists a non-sealed batch (i.e. there is one+ stored miniblock that isn't assigned
     /// to any batch yet).
     pub async fn pending_batch_exists(&mut self) -> sqlx::Result<bool> {
@@ -2050,6 +2149,36 @@ impl BlocksDal<'_, '_> {
         Ok(Some((v as u16).try_into()?))
     }
 
+    /// Returns distinct protocol versions of the miniblocks in the specified range, in ascending order.
+    pub async fn protocol_versions_in_range(
+        &mut self,
+        numbers: ops::RangeInclusive<MiniblockNumber>,
+    ) -> anyhow::Result<Vec<ProtocolVersionId>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT
+                protocol_version
+            FROM
+                miniblocks
+            WHERE
+                number BETWEEN $1 AND $2
+                AND protocol_version IS NOT NULL
+            ORDER BY
+                protocol_version
+            "#,
+            numbers.start().0 as i64,
+            numbers.end().0 as i64
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        let mut versions = Vec::with_capacity(rows.len());
+        for version in rows.into_iter().filter_map(|row| row.protocol_version) {
+            versions.push((version as u16).try_into()?);
+        }
+        Ok(versions)
+    }
+
     pub async fn get_miniblock_timestamp(
         &mut self,
         miniblock_number: MiniblockNumber,
@@ -2199,6 +2328,184 @@ impl BlocksDal<'_, '_> {
             .map(|address| address != Address::default()))
     }
 
+    /// Checks whether `fee_account_address` is migrated for the miniblocks of the specified L1 batch.
+    /// Returns an error if the L1 batch is not sealed.
+    pub async fn is_fee_address_migrated_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<bool> {
+        let (first_miniblock, last_miniblock) = self
+            .get_miniblock_range_of_l1_batch(l1_batch_number)
+            .await?
+            .with_context(|| format!("L1 batch #{l1_batch_number} is not sealed"))?;
+        // Miniblocks are migrated in increasing order, so it's sufficient to check the range endpoints.
+        for miniblock_number in [first_miniblock, last_miniblock] {
+            let is_migrated = self
+                .is_fee_address_migrated(miniblock_number)
+                .await?
+                .with_context(|| format!("Miniblock #{miniblock_number} disappeared"))?;
+            if !is_migrated {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Bulk version of [`Self::is_fee_address_migrated()`] for multiple miniblock chunks. Since miniblocks
+    /// in a chunk are migrated atomically, a chunk is considered migrated if its first miniblock is migrated.
+    /// Chunks starting with a non-existing miniblock are considered non-migrated.
+    pub async fn are_chunks_migrated(
+        &mut self,
+        chunks: &[ops::RangeInclusive<MiniblockNumber>],
+    ) -> sqlx::Result<Vec<bool>> {
+        let chunk_starts: Vec<_> = chunks.iter().map(|chunk| chunk.start().0 as i64).collect();
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                number,
+                fee_account_address
+            FROM
+                miniblocks
+            WHERE
+                number = ANY ($1)
+            "#,
+            &chunk_starts
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        let migrated_starts: HashMap<_, _> = rows
+            .into_iter()
+            .map(|row| {
+                let is_migrated =
+                    Address::from_slice(&row.fee_account_address) != Address::default();
+                (row.number, is_migrated)
+            })
+            .collect();
+        Ok(chunk_starts
+            .iter()
+            .map(|start| migrated_starts.get(start).copied().unwrap_or(false))
+            .collect())
+    }
+
+    /// Returns miniblocks in the specified range that are included into L1 batches and don't have `fee_account_address`
+    /// set, in the increasing order. If `limit` is specified, at most `limit` first miniblocks are returned.
+    /// Unlike [`Self::are_chunks_migrated()`], this checks each miniblock individually, so it detects partially
+    /// migrated chunks.
+    pub async fn unmigrated_miniblocks_in_range(
+        &mut self,
+        numbers: ops::RangeInclusive<MiniblockNumber>,
+        limit: Option<u32>,
+    ) -> sqlx::Result<Vec<MiniblockNumber>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                number
+            FROM
+                miniblocks
+            WHERE
+                number BETWEEN $1 AND $2
+                AND l1_batch_number IS NOT NULL
+                AND fee_account_address = '\x0000000000000000000000000000000000000000'::bytea
+            ORDER BY
+                number
+            LIMIT
+                $3
+            "#,
+            numbers.start().0 as i64,
+            numbers.end().0 as i64,
+            limit.map(i64::from)
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| MiniblockNumber(row.number as u32))
+            .collect())
+    }
+
+    /// Checks whether the specified range contains miniblocks returned by [`Self::unmigrated_miniblocks_in_range()`].
+    /// Unlike listing these miniblocks, this stops at the first unmigrated miniblock.
+    pub async fn has_unmigrated_miniblocks(
+        &mut self,
+        numbers: ops::RangeInclusive<MiniblockNumber>,
+    ) -> sqlx::Result<bool> {
+        let first_unmigrated = self
+            .unmigrated_miniblocks_in_range(numbers, Some(1))
+            .await?;
+        Ok(!first_unmigrated.is_empty())
+    }
+
+    /// Counts miniblocks in the specified range returned by [`Self::unmigrated_miniblocks_in_range()`]. This is intended
+    /// to verify chunks considered migrated by [`Self::are_chunks_migrated()`]; a non-zero count for such a chunk means
+    /// that it is only partially migrated.
+    pub async fn verify_chunk_consistency(
+        &mut self,
+        numbers: ops::RangeInclusive<MiniblockNumber>,
+    ) -> sqlx::Result<u64> {
+        let unmigrated = self.unmigrated_miniblocks_in_range(numbers, None).await?;
+        Ok(unmigrated.len() as u64)
+    }
+
+    /// Counts miniblocks included into L1 batches that don't have `fee_account_address` set.
+    pub async fn count_miniblocks_without_fee_address(&mut self) -> sqlx::Result<u64> {
+        let count = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                miniblocks
+            WHERE
+                l1_batch_number IS NOT NULL
+                AND fee_account_address = '\x0000000000000000000000000000000000000000'::bytea
+            "#
+        )
+        .fetch_one(self.storage.conn())
+        .await?
+        .count;
+        Ok(count as u64)
+    }
+
+    /// Returns contiguous ranges of miniblocks included into L1 batches that don't have `fee_account_address` set,
+    /// in the increasing order. This allows the migration to process only the actual gaps.
+    pub async fn unmigrated_miniblock_ranges(
+        &mut self,
+    ) -> sqlx::Result<Vec<ops::RangeInclusive<MiniblockNumber>>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                MIN(number) AS "start!",
+                MAX(number) AS "end!"
+            FROM
+                (
+                    SELECT
+                        number,
+                        number - ROW_NUMBER() OVER (
+                            ORDER BY
+                                number
+                        ) AS run
+                    FROM
+                        miniblocks
+                    WHERE
+                        l1_batch_number IS NOT NULL
+                        AND fee_account_address = '\x0000000000000000000000000000000000000000'::bytea
+                ) AS unmigrated
+            GROUP BY
+                run
+            ORDER BY
+                1
+            "#
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| MiniblockNumber(row.start as u32)..=MiniblockNumber(row.end as u32))
+            .collect())
+    }
+
     /// Copies `fee_account_address` for pending miniblocks (ones without an associated L1 batch)
     /// from the last L1 batch. Returns the number of affected rows.
     pub async fn copy_fee_account_address_for_pending_miniblocks(&mut self) -> sqlx::Result<u64> {
@@ -2269,6 +2576,103 @@ impl BlocksDal<'_, '_> {
         Ok(execution_result.rows_affected())
     }
 
+    /// Same as [`Self::copy_fee_account_address_for_miniblocks()`], but updates at most `max_rows` miniblocks
+    /// with the lowest numbers, so that a single statement doesn't run for too long. Returns the number of affected rows
+    /// and the miniblock to continue from, or `None` if all miniblocks in the range are processed. Because
+    /// the continuation point is returned explicitly, miniblocks in L1 batches with a zero fee address don't block
+    /// the progress. `max_rows` should be positive.
+    pub async fn copy_fee_account_address_for_miniblocks_limited(
+        &mut self,
+        numbers: ops::RangeInclusive<MiniblockNumber>,
+        max_rows: u32,
+    ) -> sqlx::Result<(u64, Option<MiniblockNumber>)> {
+        let rows = sqlx::query!(
+            r#"
+            UPDATE miniblocks
+            SET
+                fee_account_address = l1_batches.fee_account_address
+            FROM
+                l1_batches
+            WHERE
+                l1_batches.number = miniblocks.l1_batch_number
+                AND miniblocks.number IN (
+                    SELECT
+                        number
+                    FROM
+                        miniblocks
+                    WHERE
+                        number BETWEEN $1 AND $2
+                        AND l1_batch_number IS NOT NULL
+                        AND fee_account_address = '\x0000000000000000000000000000000000000000'::bytea
+                    ORDER BY
+                        number
+                    LIMIT
+                        $3
+                )
+            RETURNING
+                miniblocks.number
+            "#,
+            numbers.start().0 as i64,
+            numbers.end().0 as i64,
+            i64::from(max_rows)
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        let rows_affected = rows.len() as u64;
+        let last_updated = rows.iter().map(|row| row.number).max();
+        let next_miniblock = match last_updated {
+            Some(number) if rows_affected >= u64::from(max_rows) => {
+                let number = MiniblockNumber(number as u32);
+                (number < *numbers.end()).then(|| number + 1)
+            }
+            _ => None,
+        };
+        Ok((rows_affected, next_miniblock))
+    }
+
+    /// Returns the average size of a row in the `miniblocks` table in bytes, sampled from the `sample_size`
+    /// latest miniblocks. Returns `None` if there are no miniblocks.
+    pub async fn average_miniblock_row_size(
+        &mut self,
+        sample_size: u32,
+    ) -> sqlx::Result<Option<u64>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                AVG(pg_column_size(recent_miniblocks.*))::BIGINT AS "avg_size"
+            FROM
+                (
+                    SELECT
+                        *
+                    FROM
+                        miniblocks
+                    ORDER BY
+                        number DESC
+                    LIMIT
+                        $1
+                ) AS recent_miniblocks
+            "#,
+            i64::from(sample_size)
+        )
+        .fetch_one(self.storage.conn())
+        .await?;
+        Ok(row.avg_size.map(|size| size as u64))
+    }
+
+    /// Updates planner statistics for the `miniblocks` table. Should be run after bulk updates
+    /// of the table, such as the fee address migration.
+    pub async fn analyze_miniblocks_table(&mut self) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            ANALYZE miniblocks
+            "#
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
     /// Sets `fee_account_address` for an L1 batch. Should only be used in tests.
     pub async fn set_l1_batch_fee_address(
         &mut self,
@@ -2352,7 +2756,139 @@ mod tests {
     };
 
     use super::*;
-    use crate::{tests::create_miniblock_header, ConnectionPool};
+    use crate::{
+        tests::{create_miniblock_header, insert_miniblocks, seal_mock_l1_batch},
+        ConnectionPool,
+    };
+
+    /// Creates a miniblock header with the fee address set to `0x23..` if `is_migrated`, or to zero otherwise.
+    fn create_miniblock_header_with_fee_address(number: u32, is_migrated: bool) -> MiniblockHeader {
+        let fee_account_address = if is_migrated {
+            Address::repeat_byte(0x23)
+        } else {
+            Address::default()
+        };
+        MiniblockHeader {
+            fee_account_address,
+            ..create_miniblock_header(number)
+        }
+    }
+
+    #[tokio::test]
+    async fn validating_batch_existence() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+
+        // No L1 batches, not even the genesis one.
+        assert!(!conn
+            .blocks_dal()
+            .is_batch_sealed(L1BatchNumber(0))
+            .await
+            .unwrap());
+        let err = conn
+            .blocks_dal()
+            .validate_batch_exists(L1BatchNumber(0))
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            BatchNotFound {
+                requested: L1BatchNumber(0),
+                last_sealed: None,
+            }
+        );
+
+        for number in 0..2 {
+            seal_mock_l1_batch(&mut conn, number).await;
+        }
+
+        for number in [0, 1] {
+            let number = L1BatchNumber(number);
+            assert!(conn.blocks_dal().is_batch_sealed(number).await.unwrap());
+            conn.blocks_dal()
+                .validate_batch_exists(number)
+                .await
+                .unwrap()
+                .unwrap();
+        }
+
+        assert!(!conn
+            .blocks_dal()
+            .is_batch_sealed(L1BatchNumber(2))
+            .await
+            .unwrap());
+        let err = conn
+            .blocks_dal()
+            .validate_batch_exists(L1BatchNumber(100))
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            BatchNotFound {
+                requested: L1BatchNumber(100),
+                last_sealed: Some(L1BatchNumber(1)),
+            }
+        );
+        let err = err.to_string();
+        assert!(err.contains("#100 is not sealed"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn advancing_miniblock_by_batch() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, [create_miniblock_header(0)]).await;
+
+        // L1 batch #0 contains miniblock #0, #1 contains miniblocks #1 and #2, #2 contains miniblocks #3 and #4.
+        // Miniblock #5 is not included into an L1 batch.
+        seal_mock_l1_batch(&mut conn, 0).await;
+        for (batch_number, miniblocks) in [(1, [1, 2]), (2, [3, 4])] {
+            for number in miniblocks {
+                conn.blocks_dal()
+                    .insert_miniblock(&create_miniblock_header(number))
+                    .await
+                    .unwrap();
+            }
+            seal_mock_l1_batch(&mut conn, batch_number).await;
+        }
+        conn.blocks_dal()
+            .insert_miniblock(&create_miniblock_header(5))
+            .await
+            .unwrap();
+
+        let mut miniblock = MiniblockNumber(0);
+        let mut visited_miniblocks = vec![miniblock];
+        while let Some(next_miniblock) =
+            conn.blocks_dal().advance_by_batch(miniblock).await.unwrap()
+        {
+            visited_miniblocks.push(next_miniblock);
+            miniblock = next_miniblock;
+        }
+        assert_eq!(
+            visited_miniblocks,
+            [MiniblockNumber(0), MiniblockNumber(1), MiniblockNumber(3)]
+        );
+
+        let next_miniblock = conn
+            .blocks_dal()
+            .advance_by_batch(MiniblockNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(next_miniblock, Some(MiniblockNumber(3)));
+        for number in [4, 5, 6] {
+            let next_miniblock = conn
+                .blocks_dal()
+                .advance_by_batch(MiniblockNumber(number))
+                .await
+                .unwrap();
+            assert_eq!(next_miniblock, None);
+        }
+    }
 
     #[tokio::test]
     async fn loading_l1_batch_header() {
@@ -2470,6 +3006,107 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn getting_miniblock_count_for_l1_batch() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, (0..3).map(create_miniblock_header)).await;
+        seal_mock_l1_batch(&mut conn, 1).await;
+
+        let count = conn
+            .blocks_dal()
+            .miniblock_count_for_l1_batch(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(count, 3);
+        let count = conn
+            .blocks_dal()
+            .miniblock_count_for_l1_batch(L1BatchNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[tokio::test]
+    async fn getting_batch_for_miniblock() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, (0..3).map(create_miniblock_header)).await;
+        seal_mock_l1_batch(&mut conn, 1).await;
+        // Miniblock #3 is pending.
+        conn.blocks_dal()
+            .insert_miniblock(&create_miniblock_header(3))
+            .await
+            .unwrap();
+
+        for number in 0..3 {
+            let l1_batch_number = conn
+                .blocks_dal()
+                .get_batch_for_miniblock(MiniblockNumber(number))
+                .await
+                .unwrap();
+            assert_eq!(l1_batch_number, Some(L1BatchNumber(1)));
+        }
+        for number in [3, 100] {
+            let l1_batch_number = conn
+                .blocks_dal()
+                .get_batch_for_miniblock(MiniblockNumber(number))
+                .await
+                .unwrap();
+            assert_eq!(l1_batch_number, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn getting_protocol_versions_in_miniblock_range() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        let prev_version: ProtocolVersionId =
+            (ProtocolVersionId::latest() as u16 - 1).try_into().unwrap();
+        for id in [prev_version, ProtocolVersionId::latest()] {
+            conn.protocol_versions_dal()
+                .save_protocol_version_with_tx(ProtocolVersion {
+                    id,
+                    ..ProtocolVersion::default()
+                })
+                .await;
+        }
+        for number in 0..4 {
+            let protocol_version = if number < 2 {
+                prev_version
+            } else {
+                ProtocolVersionId::latest()
+            };
+            let miniblock = MiniblockHeader {
+                protocol_version: Some(protocol_version),
+                ..create_miniblock_header(number)
+            };
+            conn.blocks_dal()
+                .insert_miniblock(&miniblock)
+                .await
+                .unwrap();
+        }
+
+        let versions = conn
+            .blocks_dal()
+            .protocol_versions_in_range(MiniblockNumber(0)..=MiniblockNumber(3))
+            .await
+            .unwrap();
+        assert_eq!(versions, [prev_version, ProtocolVersionId::latest()]);
+        let versions = conn
+            .blocks_dal()
+            .protocol_versions_in_range(MiniblockNumber(2)..=MiniblockNumber(10))
+            .await
+            .unwrap();
+        assert_eq!(versions, [ProtocolVersionId::latest()]);
+        let versions = conn
+            .blocks_dal()
+            .protocol_versions_in_range(MiniblockNumber(5)..=MiniblockNumber(10))
+            .await
+            .unwrap();
+        assert!(versions.is_empty());
+    }
+
     #[allow(deprecated)] // that's the whole point
     #[tokio::test]
     async fn checking_fee_account_address_in_l1_batches() {
@@ -2609,4 +3246,225 @@ mod tests {
             );
         }
     }
+
+    #[allow(deprecated)] // that's the whole point
+    #[tokio::test]
+    async fn copying_fee_account_address_with_limit() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, (1..=3).map(create_miniblock_header)).await;
+        seal_mock_l1_batch(&mut conn, 1).await;
+        conn.blocks_dal()
+            .set_l1_batch_fee_address(L1BatchNumber(1), Address::repeat_byte(0x23))
+            .await
+            .unwrap();
+
+        let range = MiniblockNumber(0)..=MiniblockNumber(100);
+        let output = conn
+            .blocks_dal()
+            .copy_fee_account_address_for_miniblocks_limited(range.clone(), 2)
+            .await
+            .unwrap();
+        assert_eq!(output, (2, Some(MiniblockNumber(3))));
+        for (number, is_migrated) in [(1, true), (2, true), (3, false)] {
+            assert_eq!(
+                conn.blocks_dal()
+                    .is_fee_address_migrated(MiniblockNumber(number))
+                    .await
+                    .unwrap(),
+                Some(is_migrated)
+            );
+        }
+
+        let output = conn
+            .blocks_dal()
+            .copy_fee_account_address_for_miniblocks_limited(MiniblockNumber(3)..=*range.end(), 2)
+            .await
+            .unwrap();
+        assert_eq!(output, (1, None));
+        let output = conn
+            .blocks_dal()
+            .copy_fee_account_address_for_miniblocks_limited(range, 2)
+            .await
+            .unwrap();
+        assert_eq!(output, (0, None));
+
+        for number in 1..=3 {
+            let fee_address = conn
+                .blocks_dal()
+                .raw_fee_address_for_miniblock(MiniblockNumber(number))
+                .await
+                .unwrap();
+            assert_eq!(fee_address, Some(Address::repeat_byte(0x23)));
+        }
+    }
+
+    #[allow(deprecated)] // that's the whole point
+    #[tokio::test]
+    async fn checking_fee_address_migration_for_l1_batch() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, [1, 2].map(create_miniblock_header)).await;
+        seal_mock_l1_batch(&mut conn, 1).await;
+        conn.blocks_dal()
+            .set_l1_batch_fee_address(L1BatchNumber(1), Address::repeat_byte(0x23))
+            .await
+            .unwrap();
+
+        assert!(!conn
+            .blocks_dal()
+            .is_fee_address_migrated_for_l1_batch(L1BatchNumber(1))
+            .await
+            .unwrap());
+
+        conn.blocks_dal()
+            .copy_fee_account_address_for_miniblocks(MiniblockNumber(0)..=MiniblockNumber(2))
+            .await
+            .unwrap();
+        assert!(conn
+            .blocks_dal()
+            .is_fee_address_migrated_for_l1_batch(L1BatchNumber(1))
+            .await
+            .unwrap());
+
+        // L1 batch #2 is not sealed.
+        conn.blocks_dal()
+            .is_fee_address_migrated_for_l1_batch(L1BatchNumber(2))
+            .await
+            .unwrap_err();
+    }
+
+    #[allow(deprecated)] // that's the whole point
+    #[tokio::test]
+    async fn getting_unmigrated_miniblock_ranges() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        // Miniblocks #3 and #4 are migrated.
+        let miniblocks = (1..=6).map(|number| {
+            create_miniblock_header_with_fee_address(number, (3..=4).contains(&number))
+        });
+        insert_miniblocks(&mut conn, miniblocks).await;
+        seal_mock_l1_batch(&mut conn, 1).await;
+        // Pending miniblocks must not be returned.
+        conn.blocks_dal()
+            .insert_miniblock(&create_miniblock_header(7))
+            .await
+            .unwrap();
+
+        let ranges = conn
+            .blocks_dal()
+            .unmigrated_miniblock_ranges()
+            .await
+            .unwrap();
+        assert_eq!(
+            ranges,
+            [
+                MiniblockNumber(1)..=MiniblockNumber(2),
+                MiniblockNumber(5)..=MiniblockNumber(6)
+            ]
+        );
+
+        conn.blocks_dal()
+            .set_l1_batch_fee_address(L1BatchNumber(1), Address::repeat_byte(0x23))
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .copy_fee_account_address_for_miniblocks(MiniblockNumber(0)..=MiniblockNumber(6))
+            .await
+            .unwrap();
+        let ranges = conn
+            .blocks_dal()
+            .unmigrated_miniblock_ranges()
+            .await
+            .unwrap();
+        assert!(ranges.is_empty());
+    }
+
+    #[allow(deprecated)] // that's the whole point
+    #[tokio::test]
+    async fn checking_migration_status_for_chunks() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        // Miniblocks #0..=#2 are migrated.
+        let miniblocks =
+            (0..6).map(|number| create_miniblock_header_with_fee_address(number, number <= 2));
+        insert_miniblocks(&mut conn, miniblocks).await;
+
+        let chunks: Vec<_> = (0..4)
+            .map(|i| MiniblockNumber(i * 2)..=MiniblockNumber(i * 2 + 1))
+            .collect();
+        let statuses = conn
+            .blocks_dal()
+            .are_chunks_migrated(&chunks)
+            .await
+            .unwrap();
+        assert_eq!(statuses, [true, true, false, false]);
+
+        for (chunk, is_migrated) in chunks.iter().zip(statuses) {
+            let expected = conn
+                .blocks_dal()
+                .is_fee_address_migrated(*chunk.start())
+                .await
+                .unwrap()
+                .unwrap_or(false);
+            assert_eq!(is_migrated, expected, "{chunk:?}");
+        }
+
+        let statuses = conn.blocks_dal().are_chunks_migrated(&[]).await.unwrap();
+        assert!(statuses.is_empty());
+    }
+
+    #[allow(deprecated)] // that's the whole point
+    #[tokio::test]
+    async fn getting_unmigrated_miniblocks_in_partially_migrated_chunk() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        // All miniblocks except for #2 are migrated.
+        let miniblocks =
+            (0..5).map(|number| create_miniblock_header_with_fee_address(number, number != 2));
+        insert_miniblocks(&mut conn, miniblocks).await;
+        seal_mock_l1_batch(&mut conn, 1).await;
+
+        let chunk = MiniblockNumber(0)..=MiniblockNumber(4);
+        let statuses = conn
+            .blocks_dal()
+            .are_chunks_migrated(&[chunk.clone()])
+            .await
+            .unwrap();
+        assert_eq!(statuses, [true]); // the chunk-level check doesn't notice the gap
+        let unmigrated = conn
+            .blocks_dal()
+            .unmigrated_miniblocks_in_range(chunk.clone(), None)
+            .await
+            .unwrap();
+        assert_eq!(unmigrated, [MiniblockNumber(2)]);
+        assert!(conn
+            .blocks_dal()
+            .has_unmigrated_miniblocks(chunk.clone())
+            .await
+            .unwrap());
+        let unmigrated_count = conn
+            .blocks_dal()
+            .verify_chunk_consistency(chunk)
+            .await
+            .unwrap();
+        assert_eq!(unmigrated_count, 1);
+        let unmigrated_count = conn
+            .blocks_dal()
+            .verify_chunk_consistency(MiniblockNumber(3)..=MiniblockNumber(10))
+            .await
+            .unwrap();
+        assert_eq!(unmigrated_count, 0);
+        let unmigrated = conn
+            .blocks_dal()
+            .unmigrated_miniblocks_in_range(MiniblockNumber(3)..=MiniblockNumber(10), None)
+            .await
+            .unwrap();
+        assert!(unmigrated.is_empty());
+        assert!(!conn
+            .blocks_dal()
+            .has_unmigrated_miniblocks(MiniblockNumber(3)..=MiniblockNumber(10))
+            .await
+            .unwrap());
+    }
 }
@@ -1,11 +1,13 @@
-use std::{collections::HashMap, fmt};
+use std::{collections::HashMap, fmt, iter, ops};
 
+use anyhow::Context as _;
 use sqlx::types::chrono::Utc;
 use zksync_types::{
     api,
+    event::TRANSFER_EVENT_TOPIC,
     l2_to_l1_log::{L2ToL1Log, UserL2ToL1Log},
     tx::IncludedTxLocation,
-    MiniblockNumber, VmEvent, H256,
+    Address, L1BatchNumber, MiniblockNumber, VmEvent, H256, L2_ETH_TOKEN_ADDRESS,
 };
 
 use crate::{
@@ -33,12 +35,19 @@ pub struct EventsDal<'a, 'c> {
 }
 
 impl EventsDal<'_, '_> {
-    /// Saves events for the specified miniblock.
+    /// Saves events for the specified miniblock, which is being sealed into the L1 batch `l1_batch_number`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the events belong to different L1 batches, or to an L1 batch other than `l1_batch_number`.
     pub async fn save_events(
         &mut self,
         block_number: MiniblockNumber,
+        l1_batch_number: L1BatchNumber,
         all_block_events: &[(IncludedTxLocation, Vec<&VmEvent>)],
-    ) {
+    ) -> anyhow::Result<()> {
+        Self::check_event_batches(block_number, l1_batch_number, all_block_events)?;
+
         let mut copy = self
             .storage
             .conn()
@@ -53,7 +62,7 @@ impl EventsDal<'_, '_> {
                 FROM STDIN WITH (DELIMITER '|')",
             )
             .await
-            .unwrap();
+            .context("Failed starting `COPY` for events")?;
 
         let mut buffer = String::new();
         let now = Utc::now().naive_utc().to_string();
@@ -89,9 +98,39 @@ impl EventsDal<'_, '_> {
                 event_index_in_block += 1;
             }
         }
-        copy.send(buffer.as_bytes()).await.unwrap();
+        copy.send(buffer.as_bytes())
+            .await
+            .context("Failed sending events to `COPY`")?;
         // note: all the time spent in this function is spent in `copy.finish()`
-        copy.finish().await.unwrap();
+        copy.finish()
+            .await
+            .context("Failed finishing `COPY` for events")?;
+        Ok(())
+    }
+
+    fn check_event_batches(
+        block_number: MiniblockNumber,
+        expected_l1_batch: L1BatchNumber,
+        all_block_events: &[(IncludedTxLocation, Vec<&VmEvent>)],
+    ) -> anyhow::Result<()> {
+        let mut events = all_block_events.iter().flat_map(|(_, events)| events);
+        let Some(first_event) = events.next() else {
+            return Ok(());
+        };
+        let (l1_batch_number, _) = first_event.location;
+        for event in events {
+            anyhow::ensure!(
+                event.location.0 == l1_batch_number,
+                "Events for miniblock #{block_number} belong to different L1 batches: #{l1_batch_number} and #{}",
+                event.location.0
+            );
+        }
+        anyhow::ensure!(
+            l1_batch_number == expected_l1_batch,
+            "Events belong to L1 batch #{l1_batch_number}, while miniblock #{block_number} \
+             is sealed into L1 batch #{expected_l1_batch}"
+        );
+        Ok(())
     }
 
     /// Removes events with a block number strictly greater than the specified `block_number`.
@@ -186,6 +225,173 @@ impl EventsDal<'_, '_> {
         .unwrap();
     }
 
+    /// Returns the miniblock with the most events in the specified range together with its number of events.
+    /// If several miniblocks have the same number of events, the earliest one is returned.
+    pub async fn miniblock_with_most_events(
+        &mut self,
+        range: ops::RangeInclusive<MiniblockNumber>,
+    ) -> Result<Option<(MiniblockNumber, u64)>, SqlxError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number,
+                COUNT(*) AS "count!"
+            FROM
+                events
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            GROUP BY
+                miniblock_number
+            ORDER BY
+                COUNT(*) DESC,
+                miniblock_number ASC
+            LIMIT
+                1
+            "#,
+            range.start().0 as i64,
+            range.end().0 as i64
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+
+        Ok(row.map(|row| {
+            (
+                MiniblockNumber(row.miniblock_number as u32),
+                row.count as u64,
+            )
+        }))
+    }
+
+    /// Computes the distribution of miniblocks in the specified range by the number of events in them.
+    /// `buckets` are ascending lower bounds of the event count buckets; the last bucket is unbounded. If the first bound
+    /// is positive, an additional bucket for smaller event counts is added. Returns event count ranges together
+    /// with the number of miniblocks in each of them; all buckets are returned, even empty ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buckets` are empty, not strictly increasing, or exceed `i64::MAX`.
+    pub async fn event_count_histogram(
+        &mut self,
+        range: ops::RangeInclusive<MiniblockNumber>,
+        buckets: &[u64],
+    ) -> Result<Vec<(ops::Range<u64>, u64)>, SqlxError> {
+        assert!(!buckets.is_empty(), "no buckets provided");
+        assert!(
+            buckets.windows(2).all(|window| window[0] < window[1]),
+            "buckets must be strictly increasing"
+        );
+        let mut bounds = Vec::with_capacity(buckets.len() + 1);
+        if buckets[0] > 0 {
+            bounds.push(0);
+        }
+        bounds.extend(
+            buckets
+                .iter()
+                .map(|&bound| i64::try_from(bound).expect("bucket bound exceeds i64::MAX")),
+        );
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                WIDTH_BUCKET(event_count, $3::BIGINT[]) AS "bucket!",
+                COUNT(*) AS "miniblock_count!"
+            FROM
+                (
+                    SELECT
+                        miniblocks.number,
+                        COUNT(events.miniblock_number) AS event_count
+                    FROM
+                        miniblocks
+                        LEFT JOIN events ON events.miniblock_number = miniblocks.number
+                    WHERE
+                        miniblocks.number BETWEEN $1 AND $2
+                    GROUP BY
+                        miniblocks.number
+                ) AS counts
+            GROUP BY
+                1
+            "#,
+            range.start().0 as i64,
+            range.end().0 as i64,
+            &bounds
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        let bounds: Vec<_> = bounds.into_iter().map(|bound| bound as u64).collect();
+        let mut histogram: Vec<_> = bounds
+            .windows(2)
+            .map(|window| window[0]..window[1])
+            .chain(iter::once(bounds[bounds.len() - 1]..u64::MAX))
+            .map(|range| (range, 0))
+            .collect();
+        for row in rows {
+            // Since the first bound is 0, buckets are 1-based.
+            histogram[row.bucket as usize - 1].1 = row.miniblock_count as u64;
+        }
+        Ok(histogram)
+    }
+
+    /// Counts ETH transfer events (i.e., `Transfer` events emitted by the L2 ETH token) in the specified
+    /// miniblock range.
+    pub async fn count_eth_transfers(
+        &mut self,
+        range: ops::RangeInclusive<MiniblockNumber>,
+    ) -> Result<u64, SqlxError> {
+        let count = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                events
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+                AND address = $3
+                AND topic1 = $4
+            "#,
+            range.start().0 as i64,
+            range.end().0 as i64,
+            L2_ETH_TOKEN_ADDRESS.as_bytes(),
+            TRANSFER_EVENT_TOPIC.as_bytes()
+        )
+        .fetch_one(self.storage.conn())
+        .await?
+        .count;
+        Ok(count as u64)
+    }
+
+    /// Returns distinct addresses of contracts that emitted at least one event in the specified miniblock range,
+    /// ordered by address. Returns an empty vector for an empty range.
+    pub async fn distinct_emitter_addresses(
+        &mut self,
+        range: ops::RangeInclusive<MiniblockNumber>,
+    ) -> Result<Vec<Address>, SqlxError> {
+        if range.is_empty() {
+            return Ok(vec![]);
+        }
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT
+                address
+            FROM
+                events
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            ORDER BY
+                address
+            "#,
+            range.start().0 as i64,
+            range.end().0 as i64
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Address::from_slice(&row.address))
+            .collect())
+    }
+
     pub(crate) async fn get_logs_by_tx_hashes(
         &mut self,
         hashes: &[H256],
@@ -290,10 +496,16 @@ impl EventsDal<'_, '_> {
 
 #[cfg(test)]
 mod tests {
-    use zksync_types::{Address, L1BatchNumber, ProtocolVersion};
+    use zksync_types::{
+        api::{GetLogsFilter, LogsFilterBound},
+        Address, L1BatchNumber, ProtocolVersion,
+    };
 
     use super::*;
-    use crate::{tests::create_miniblock_header, ConnectionPool};
+    use crate::{
+        tests::{create_miniblock_header, insert_miniblocks, mock_tx_location, save_tx_events},
+        ConnectionPool,
+    };
 
     fn create_vm_event(index: u8, topic_count: u8) -> VmEvent {
         assert!(topic_count <= 4);
@@ -343,8 +555,9 @@ mod tests {
             (second_location, second_events.iter().collect()),
         ];
         conn.events_dal()
-            .save_events(MiniblockNumber(1), &all_events)
-            .await;
+            .save_events(MiniblockNumber(1), L1BatchNumber(1), &all_events)
+            .await
+            .unwrap();
 
         let logs = conn
             .events_web3_dal()
@@ -370,6 +583,467 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn rejecting_events_with_mismatched_l1_batch() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, [create_miniblock_header(1)]).await;
+        let location = mock_tx_location(H256([1; 32]), 0);
+
+        // Events within a miniblock must belong to the same L1 batch.
+        let mismatched_event = VmEvent {
+            location: (L1BatchNumber(2), 1),
+            ..create_vm_event(1, 0)
+        };
+        let events = [create_vm_event(0, 0), mismatched_event];
+        let err = conn
+            .events_dal()
+            .save_events(
+                MiniblockNumber(1),
+                L1BatchNumber(1),
+                &[(location, events.iter().collect())],
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("different L1 batches"), "{err}");
+
+        // Miniblock #1 is sealed into L1 batch #1, so events from L1 batch #2 must be rejected.
+        let events = [VmEvent {
+            location: (L1BatchNumber(2), 0),
+            ..create_vm_event(0, 0)
+        }];
+        let err = conn
+            .events_dal()
+            .save_events(
+                MiniblockNumber(1),
+                L1BatchNumber(1),
+                &[(location, events.iter().collect())],
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("sealed into L1 batch #1"), "{err}");
+
+        let events = [create_vm_event(0, 0)];
+        conn.events_dal()
+            .save_events(
+                MiniblockNumber(1),
+                L1BatchNumber(1),
+                &[(location, events.iter().collect())],
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn filtering_logs_by_initiator() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, [create_miniblock_header(1)]).await;
+
+        let first_location = IncludedTxLocation {
+            tx_initiator_address: Address::repeat_byte(1),
+            ..mock_tx_location(H256([1; 32]), 0)
+        };
+        let first_events = vec![create_vm_event(0, 0), create_vm_event(1, 4)];
+        let second_location = IncludedTxLocation {
+            tx_initiator_address: Address::repeat_byte(2),
+            ..mock_tx_location(H256([2; 32]), 1)
+        };
+        let second_events = vec![create_vm_event(2, 2), create_vm_event(3, 3)];
+        let all_events = vec![
+            (first_location, first_events.iter().collect()),
+            (second_location, second_events.iter().collect()),
+        ];
+        conn.events_dal()
+            .save_events(MiniblockNumber(1), L1BatchNumber(1), &all_events)
+            .await
+            .unwrap();
+
+        let filter = GetLogsFilter {
+            initiator: Some(Address::repeat_byte(2)),
+            ..GetLogsFilter::for_miniblock_range(
+                MiniblockNumber(0)..=MiniblockNumber(1),
+                vec![],
+                vec![],
+            )
+        };
+        let logs = conn
+            .events_web3_dal()
+            .get_logs(filter.clone(), 100)
+            .await
+            .unwrap();
+        let log_addresses: Vec<_> = logs.iter().map(|log| log.address).collect();
+        assert_eq!(
+            log_addresses,
+            [Address::repeat_byte(2), Address::repeat_byte(3)]
+        );
+        assert!(logs
+            .iter()
+            .all(|log| log.transaction_hash == Some(H256([2; 32]))));
+
+        let filter = GetLogsFilter {
+            initiator: Some(Address::repeat_byte(3)),
+            ..filter
+        };
+        let logs = conn.events_web3_dal().get_logs(filter, 100).await.unwrap();
+        assert!(logs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn filtering_logs_with_symbolic_bounds() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, (1..=3).map(create_miniblock_header)).await;
+        for number in 1..=3 {
+            save_tx_events(&mut conn, number, &[create_vm_event(number as u8, 0)]).await;
+        }
+
+        let bounds = [
+            (
+                LogsFilterBound::Earliest,
+                LogsFilterBound::Latest,
+                vec![1, 2, 3],
+            ),
+            (LogsFilterBound::Latest, LogsFilterBound::Latest, vec![3]),
+            (
+                LogsFilterBound::Earliest,
+                LogsFilterBound::Earliest,
+                vec![1],
+            ),
+            (
+                MiniblockNumber(2).into(),
+                LogsFilterBound::Latest,
+                vec![2, 3],
+            ),
+            (
+                LogsFilterBound::Earliest,
+                MiniblockNumber(2).into(),
+                vec![1, 2],
+            ),
+        ];
+        for (from_block, to_block, expected_miniblocks) in bounds {
+            let filter = GetLogsFilter {
+                from_block,
+                to_block,
+                addresses: vec![],
+                topics: vec![],
+                initiator: None,
+            };
+            let logs = conn.events_web3_dal().get_logs(filter, 100).await.unwrap();
+            let miniblocks: Vec<_> = logs
+                .iter()
+                .map(|log| log.block_number.unwrap().as_u32())
+                .collect();
+            assert_eq!(
+                miniblocks, expected_miniblocks,
+                "{from_block:?}..={to_block:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn filtering_logs_for_single_block() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, (1..=3).map(create_miniblock_header)).await;
+        for number in 1..=3 {
+            let events = [create_vm_event(0, 1), create_vm_event(1, 2)];
+            save_tx_events(&mut conn, number, &events).await;
+        }
+
+        let filter = GetLogsFilter::single_block(MiniblockNumber(2));
+        let logs = conn.events_web3_dal().get_logs(filter, 100).await.unwrap();
+        let log_positions: Vec<_> = logs
+            .iter()
+            .map(|log| {
+                let block_number = log.block_number.unwrap().as_u32();
+                (block_number, log.log_index.unwrap().as_u64())
+            })
+            .collect();
+        assert_eq!(log_positions, [(2, 0), (2, 1)]);
+
+        let filter = GetLogsFilter::single_block(MiniblockNumber(4));
+        let logs = conn.events_web3_dal().get_logs(filter, 100).await.unwrap();
+        assert!(logs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn getting_miniblock_with_most_events() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, (1..=4).map(create_miniblock_header)).await;
+        for (number, event_count) in [(1, 1), (2, 3), (3, 2), (4, 3)] {
+            let events: Vec<_> = (0..event_count).map(|i| create_vm_event(i, 1)).collect();
+            save_tx_events(&mut conn, number, &events).await;
+        }
+
+        let max = conn
+            .events_dal()
+            .miniblock_with_most_events(MiniblockNumber(0)..=MiniblockNumber(10))
+            .await
+            .unwrap();
+        assert_eq!(max, Some((MiniblockNumber(2), 3)));
+        let max = conn
+            .events_dal()
+            .miniblock_with_most_events(MiniblockNumber(3)..=MiniblockNumber(3))
+            .await
+            .unwrap();
+        assert_eq!(max, Some((MiniblockNumber(3), 2)));
+        let max = conn
+            .events_dal()
+            .miniblock_with_most_events(MiniblockNumber(5)..=MiniblockNumber(10))
+            .await
+            .unwrap();
+        assert_eq!(max, None);
+    }
+
+    #[tokio::test]
+    async fn getting_logical_indexes_since_block() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, (1..=4).map(create_miniblock_header)).await;
+        for number in 1..=4 {
+            let first_location = mock_tx_location(H256::repeat_byte(number as u8), 0);
+            let second_location = mock_tx_location(H256::repeat_byte(0x80 + number as u8), 1);
+            let first_events = [create_vm_event(0, 1), create_vm_event(1, 1)];
+            let second_events = [create_vm_event(2, 1)];
+            conn.events_dal()
+                .save_events(
+                    MiniblockNumber(number),
+                    L1BatchNumber(1),
+                    &[
+                        (first_location, first_events.iter().collect()),
+                        (second_location, second_events.iter().collect()),
+                    ],
+                )
+                .await
+                .unwrap();
+        }
+
+        let indexes = conn
+            .events_web3_dal()
+            .logical_indexes_since(MiniblockNumber(2))
+            .await
+            .unwrap();
+        let expected_indexes: Vec<_> = (3..=4)
+            .flat_map(|number| {
+                let number = MiniblockNumber(number);
+                [(number, 0, 0), (number, 1, 1), (number, 2, 0)]
+            })
+            .collect();
+        assert_eq!(indexes, expected_indexes);
+
+        let indexes = conn
+            .events_web3_dal()
+            .logical_indexes_since(MiniblockNumber(4))
+            .await
+            .unwrap();
+        assert!(indexes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn counting_eth_transfers() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, (1..=4).map(create_miniblock_header)).await;
+        for number in 1..=4 {
+            let eth_transfer = VmEvent {
+                address: L2_ETH_TOKEN_ADDRESS,
+                indexed_topics: vec![
+                    *TRANSFER_EVENT_TOPIC,
+                    H256::repeat_byte(1),
+                    H256::repeat_byte(2),
+                ],
+                ..create_vm_event(0, 0)
+            };
+            // A `Transfer` event emitted by another token must not be counted.
+            let token_transfer = VmEvent {
+                address: Address::repeat_byte(0x42),
+                indexed_topics: vec![*TRANSFER_EVENT_TOPIC],
+                ..create_vm_event(1, 0)
+            };
+            let events = [eth_transfer, token_transfer, create_vm_event(2, 2)];
+            save_tx_events(&mut conn, number, &events).await;
+        }
+
+        let count = conn
+            .events_dal()
+            .count_eth_transfers(MiniblockNumber(0)..=MiniblockNumber(10))
+            .await
+            .unwrap();
+        assert_eq!(count, 4);
+        let count = conn
+            .events_dal()
+            .count_eth_transfers(MiniblockNumber(2)..=MiniblockNumber(3))
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+        let count = conn
+            .events_dal()
+            .count_eth_transfers(MiniblockNumber(5)..=MiniblockNumber(10))
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[tokio::test]
+    async fn computing_event_count_histogram() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, (1..=5).map(create_miniblock_header)).await;
+        for (number, event_count) in [(1, 0), (2, 1), (3, 3), (4, 10), (5, 25)] {
+            let events: Vec<_> = (0..event_count).map(|i| create_vm_event(i, 0)).collect();
+            save_tx_events(&mut conn, number, &events).await;
+        }
+
+        let all_miniblocks = MiniblockNumber(0)..=MiniblockNumber(10);
+        let histogram = conn
+            .events_dal()
+            .event_count_histogram(all_miniblocks.clone(), &[1, 5, 20])
+            .await
+            .unwrap();
+        assert_eq!(
+            histogram,
+            [(0..1, 1), (1..5, 2), (5..20, 1), (20..u64::MAX, 1)]
+        );
+
+        let histogram = conn
+            .events_dal()
+            .event_count_histogram(all_miniblocks, &[0, 100])
+            .await
+            .unwrap();
+        assert_eq!(histogram, [(0..100, 5), (100..u64::MAX, 0)]);
+
+        let histogram = conn
+            .events_dal()
+            .event_count_histogram(MiniblockNumber(2)..=MiniblockNumber(3), &[2])
+            .await
+            .unwrap();
+        assert_eq!(histogram, [(0..2, 1), (2..u64::MAX, 1)]);
+    }
+
+    #[tokio::test]
+    async fn checking_logical_order_of_events() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, [create_miniblock_header(1)]).await;
+
+        let is_consistent = conn
+            .events_web3_dal()
+            .is_block_logical_order_consistent(MiniblockNumber(1))
+            .await
+            .unwrap();
+        assert!(is_consistent);
+
+        let first_location = mock_tx_location(H256([1; 32]), 0);
+        let first_events = [create_vm_event(0, 1), create_vm_event(1, 2)];
+        let second_location = mock_tx_location(H256([2; 32]), 1);
+        let second_events = [create_vm_event(2, 0), create_vm_event(3, 3)];
+        let all_events = [
+            (first_location, first_events.iter().collect()),
+            (second_location, second_events.iter().collect()),
+        ];
+        conn.events_dal()
+            .save_events(MiniblockNumber(1), L1BatchNumber(1), &all_events)
+            .await
+            .unwrap();
+
+        let is_consistent = conn
+            .events_web3_dal()
+            .is_block_logical_order_consistent(MiniblockNumber(1))
+            .await
+            .unwrap();
+        assert!(is_consistent);
+
+        // Manually corrupt the index of the last event in the second transaction.
+        sqlx::query(
+            "UPDATE events SET event_index_in_tx = 0 \
+             WHERE miniblock_number = 1 AND event_index_in_block = 3",
+        )
+        .execute(conn.conn())
+        .await
+        .unwrap();
+        let is_consistent = conn
+            .events_web3_dal()
+            .is_block_logical_order_consistent(MiniblockNumber(1))
+            .await
+            .unwrap();
+        assert!(!is_consistent);
+    }
+
+    #[tokio::test]
+    async fn getting_distinct_emitter_addresses() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, (1..=3).map(create_miniblock_header)).await;
+
+        // Event indexes determine emitter addresses, see `create_vm_event()`.
+        let event_indexes: [&[u8]; 3] = [&[3, 1, 3], &[2], &[1, 5]];
+        for (number, indexes) in (1..).zip(event_indexes) {
+            let events: Vec<_> = indexes.iter().map(|&i| create_vm_event(i, 1)).collect();
+            save_tx_events(&mut conn, number, &events).await;
+        }
+
+        let addresses = conn
+            .events_dal()
+            .distinct_emitter_addresses(MiniblockNumber(0)..=MiniblockNumber(10))
+            .await
+            .unwrap();
+        let expected: Vec<_> = [1, 2, 3, 5].into_iter().map(Address::repeat_byte).collect();
+        assert_eq!(addresses, expected);
+
+        let addresses = conn
+            .events_dal()
+            .distinct_emitter_addresses(MiniblockNumber(1)..=MiniblockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            addresses,
+            [Address::repeat_byte(1), Address::repeat_byte(3)]
+        );
+
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty_range = MiniblockNumber(3)..=MiniblockNumber(1);
+        let addresses = conn
+            .events_dal()
+            .distinct_emitter_addresses(empty_range)
+            .await
+            .unwrap();
+        assert!(addresses.is_empty());
+        let addresses = conn
+            .events_dal()
+            .distinct_emitter_addresses(MiniblockNumber(4)..=MiniblockNumber(10))
+            .await
+            .unwrap();
+        assert!(addresses.is_empty());
+    }
+
+    #[tokio::test]
+    async fn getting_recent_logs() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, (1..=3).map(create_miniblock_header)).await;
+        for number in 1..=3 {
+            let events = [create_vm_event(0, 1), create_vm_event(1, 1)];
+            save_tx_events(&mut conn, number, &events).await;
+        }
+
+        let logs = conn.events_web3_dal().get_recent_logs(3).await.unwrap();
+        let log_positions: Vec<_> = logs
+            .iter()
+            .map(|log| {
+                let block_number = log.block_number.unwrap().as_u64();
+                (block_number, log.log_index.unwrap().as_u64())
+            })
+            .collect();
+        assert_eq!(log_positions, [(3, 1), (3, 0), (2, 1)]);
+
+        let logs = conn.events_web3_dal().get_recent_logs(100).await.unwrap();
+        assert_eq!(logs.len(), 6);
+        let logs = conn.events_web3_dal().get_recent_logs(0).await.unwrap();
+        assert!(logs.is_empty());
+    }
+
     fn create_l2_to_l1_log(tx_number_in_block: u16, index: u8) -> UserL2ToL1Log {
         UserL2ToL1Log(L2ToL1Log {
             shard_id: 0,
@@ -1,7 +1,11 @@
 use sqlx::Row;
 use zksync_types::{
-    api::{GetLogsFilter, Log},
-    Address, MiniblockNumber, H256,
+    api::{self, GetLogsFilter, Log, LogsFilterBound},
+    web3::{
+        signing::keccak256,
+        types::{BlockId, BlockNumber},
+    },
+    AccountTreeId, Address, L1BatchNumber, MiniblockNumber, VmEvent, H256,
 };
 
 use crate::{
@@ -14,6 +18,37 @@ pub struct EventsWeb3Dal<'a, 'c> {
 }
 
 impl EventsWeb3Dal<'_, '_> {
+    /// Resolves a `web3` block ID (e.g., received by interop code) to a miniblock number. Block hashes are resolved
+    /// using the storage, while block numbers are passed through as is; numbers exceeding `u32::MAX` resolve to `None`
+    /// since there can be no such miniblocks. Symbolic block numbers are resolved in the same way as in the API;
+    /// `safe` is treated as `finalized`.
+    pub async fn resolve_block_id(
+        &mut self,
+        id: BlockId,
+    ) -> Result<Option<MiniblockNumber>, SqlxError> {
+        let block_id = match id {
+            BlockId::Number(BlockNumber::Number(number)) => {
+                let number = u32::try_from(number.as_u64()).ok();
+                return Ok(number.map(MiniblockNumber));
+            }
+            BlockId::Hash(hash) => api::BlockId::Hash(hash),
+            BlockId::Number(BlockNumber::Latest) => api::BlockId::Number(api::BlockNumber::Latest),
+            BlockId::Number(BlockNumber::Earliest) => {
+                api::BlockId::Number(api::BlockNumber::Earliest)
+            }
+            BlockId::Number(BlockNumber::Pending) => {
+                api::BlockId::Number(api::BlockNumber::Pending)
+            }
+            BlockId::Number(BlockNumber::Finalized | BlockNumber::Safe) => {
+                api::BlockId::Number(api::BlockNumber::Finalized)
+            }
+        };
+        self.storage
+            .blocks_web3_dal()
+            .resolve_block_id(block_id)
+            .await
+    }
+
     /// Returns miniblock number of log for given filter and offset.
     /// Used to determine if there is more than `offset` logs that satisfies filter.
     pub async fn get_log_block_number(
@@ -45,6 +80,9 @@ impl EventsWeb3Dal<'_, '_> {
                 let topics: Vec<_> = topics.iter().map(H256::as_bytes).collect();
                 query = query.bind(topics);
             }
+            if let Some(initiator) = &filter.initiator {
+                query = query.bind(initiator.as_bytes());
+            }
             query = query.bind(offset as i32);
             let log = query
                 .instrument("get_log_block_number")
@@ -97,6 +135,9 @@ impl EventsWeb3Dal<'_, '_> {
                 let topics: Vec<_> = topics.iter().map(H256::as_bytes).collect();
                 query = query.bind(topics);
             }
+            if let Some(initiator) = &filter.initiator {
+                query = query.bind(initiator.as_bytes());
+            }
             query = query.bind(limit as i32);
 
             let db_logs: Vec<StorageWeb3Log> = query
@@ -111,12 +152,52 @@ impl EventsWeb3Dal<'_, '_> {
         }
     }
 
+    /// Same as [`Self::get_logs()`], but annotates each log with whether it was emitted by a system contract
+    /// (see [`AccountTreeId::is_system_account()`]). This allows clients to hide logs emitted by system contracts.
+    pub async fn get_raw_logs_with_system_flag(
+        &mut self,
+        filter: GetLogsFilter,
+        limit: usize,
+    ) -> Result<Vec<(Log, bool)>, SqlxError> {
+        let logs = self.get_logs(filter, limit).await?;
+        Ok(logs
+            .into_iter()
+            .map(|log| {
+                let is_system = AccountTreeId::new(log.address).is_system_account();
+                (log, is_system)
+            })
+            .collect())
+    }
+
+    /// Same as [`Self::get_logs()`], but additionally returns whether the result set was truncated, i.e.
+    /// there are more than `limit` logs matching the filter. In this case, the caller can request the remaining logs
+    /// by setting `from_block` to the block of the last returned log and skipping the logs it has already seen.
+    pub async fn get_logs_with_truncation_flag(
+        &mut self,
+        filter: GetLogsFilter,
+        limit: usize,
+    ) -> Result<(Vec<Log>, bool), SqlxError> {
+        // Fetch an extra log to check whether the result set is truncated. The limit is bound as `i32`,
+        // so we cannot fetch more than `i32::MAX` logs anyway.
+        let fetched_limit = limit.saturating_add(1).min(i32::MAX as usize);
+        let mut logs = self.get_logs(filter, fetched_limit).await?;
+        let is_truncated = logs.len() > limit;
+        logs.truncate(limit);
+        Ok((logs, is_truncated))
+    }
+
     fn build_get_logs_where_clause(&self, filter: &GetLogsFilter) -> (String, u8) {
         let mut arg_index = 1;
 
-        let mut where_sql = format!("(miniblock_number >= {})", filter.from_block.0 as i64);
+        let mut where_sql = format!(
+            "(miniblock_number >= {})",
+            Self::miniblock_bound_sql(filter.from_block)
+        );
 
-        where_sql += &format!(" AND (miniblock_number <= {})", filter.to_block.0 as i64);
+        where_sql += &format!(
+            " AND (miniblock_number <= {})",
+            Self::miniblock_bound_sql(filter.to_block)
+        );
 
         if !filter.addresses.is_empty() {
             where_sql += &format!(" AND (address = ANY(${}))", arg_index);
@@ -126,10 +207,24 @@ impl EventsWeb3Dal<'_, '_> {
             where_sql += &format!(" AND (topic{} = ANY(${}))", topic_index, arg_index);
             arg_index += 1;
         }
+        if filter.initiator.is_some() {
+            where_sql += &format!(" AND (tx_initiator_address = ${})", arg_index);
+            arg_index += 1;
+        }
 
         (where_sql, arg_index)
     }
 
+    /// Returns an SQL expression for the miniblock number corresponding to `bound`. Symbolic bounds
+    /// are resolved by the query itself, so they are consistent with the returned logs.
+    fn miniblock_bound_sql(bound: LogsFilterBound) -> String {
+        match bound {
+            LogsFilterBound::Number(number) => (number.0 as i64).to_string(),
+            LogsFilterBound::Latest => "(SELECT MAX(number) FROM miniblocks)".to_owned(),
+            LogsFilterBound::Earliest => "(SELECT MIN(number) FROM miniblocks)".to_owned(),
+        }
+    }
+
     pub async fn get_all_logs(
         &mut self,
         from_block: MiniblockNumber,
@@ -189,14 +284,427 @@ impl EventsWeb3Dal<'_, '_> {
             Ok(logs)
         }
     }
+
+    /// Returns up to `limit` most recently stored logs across all miniblocks, ordered from newest to oldest.
+    pub async fn get_recent_logs(&mut self, limit: usize) -> Result<Vec<Log>, SqlxError> {
+        let db_logs: Vec<StorageWeb3Log> = sqlx::query_as!(
+            StorageWeb3Log,
+            r#"
+            WITH
+                events_select AS (
+                    SELECT
+                        address,
+                        topic1,
+                        topic2,
+                        topic3,
+                        topic4,
+                        value,
+                        miniblock_number,
+                        tx_hash,
+                        tx_index_in_block,
+                        event_index_in_block,
+                        event_index_in_tx
+                    FROM
+                        events
+                    ORDER BY
+                        miniblock_number DESC,
+                        event_index_in_block DESC
+                    LIMIT
+                        $1
+                )
+            SELECT
+                miniblocks.hash AS "block_hash?",
+                address AS "address!",
+                topic1 AS "topic1!",
+                topic2 AS "topic2!",
+                topic3 AS "topic3!",
+                topic4 AS "topic4!",
+                value AS "value!",
+                miniblock_number AS "miniblock_number!",
+                miniblocks.l1_batch_number AS "l1_batch_number?",
+                tx_hash AS "tx_hash!",
+                tx_index_in_block AS "tx_index_in_block!",
+                event_index_in_block AS "event_index_in_block!",
+                event_index_in_tx AS "event_index_in_tx!"
+            FROM
+                events_select
+                INNER JOIN miniblocks ON events_select.miniblock_number = miniblocks.number
+            ORDER BY
+                miniblock_number DESC,
+                event_index_in_block DESC
+            "#,
+            limit as i64
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+        Ok(db_logs.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns index assignments for all logs in miniblocks after `from_block`, so that external indexers
+    /// can sync incrementally. Each entry is `(miniblock_number, event_index_in_block, event_index_in_tx)`;
+    /// the first two components uniquely identify the log. Entries are ordered by the miniblock number
+    /// and the index in the miniblock.
+    pub async fn logical_indexes_since(
+        &mut self,
+        from_block: MiniblockNumber,
+    ) -> sqlx::Result<Vec<(MiniblockNumber, u32, u32)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number,
+                event_index_in_block,
+                event_index_in_tx
+            FROM
+                events
+            WHERE
+                miniblock_number > $1
+            ORDER BY
+                miniblock_number ASC,
+                event_index_in_block ASC
+            "#,
+            from_block.0 as i64
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    MiniblockNumber(row.miniblock_number as u32),
+                    row.event_index_in_block as u32,
+                    row.event_index_in_tx as u32,
+                )
+            })
+            .collect())
+    }
+
+    /// Checks whether event indexes in the specified miniblock are consistent with the order of events,
+    /// i.e., `event_index_in_block` values are `0, 1, 2, ..`, transaction indexes are non-decreasing,
+    /// and `event_index_in_tx` values restart from 0 for each transaction and increase by 1 within it.
+    /// This is a cheap check that can be used for spot checks of the stored data. Returns `true`
+    /// for miniblocks without events.
+    pub async fn is_block_logical_order_consistent(
+        &mut self,
+        block: MiniblockNumber,
+    ) -> sqlx::Result<bool> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                tx_index_in_block,
+                event_index_in_block,
+                event_index_in_tx
+            FROM
+                events
+            WHERE
+                miniblock_number = $1
+            ORDER BY
+                event_index_in_block ASC
+            "#,
+            block.0 as i64
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        let mut prev_tx_index = None;
+        let mut expected_index_in_tx = 0;
+        for (expected_index_in_block, row) in rows.into_iter().enumerate() {
+            if row.event_index_in_block as usize != expected_index_in_block {
+                return Ok(false);
+            }
+            match prev_tx_index {
+                Some(prev) if prev == row.tx_index_in_block => {}
+                Some(prev) if prev > row.tx_index_in_block => return Ok(false),
+                _ => {
+                    // New transaction
+                    prev_tx_index = Some(row.tx_index_in_block);
+                    expected_index_in_tx = 0;
+                }
+            }
+            if row.event_index_in_tx != expected_index_in_tx {
+                return Ok(false);
+            }
+            expected_index_in_tx += 1;
+        }
+        Ok(true)
+    }
+
+    /// Computes a digest of all events in the specified miniblock, which can be used for caching and reorg detection.
+    /// The digest is the hash of the concatenated content hashes of events ordered by their index in the miniblock;
+    /// the content hash of an event covers its address, topics and data. Returns `None` if the miniblock
+    /// has no events (including if it doesn't exist).
+    pub async fn block_events_digest(
+        &mut self,
+        block: MiniblockNumber,
+    ) -> sqlx::Result<Option<H256>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                address,
+                topic1,
+                topic2,
+                topic3,
+                topic4,
+                value
+            FROM
+                events
+            WHERE
+                miniblock_number = $1
+            ORDER BY
+                event_index_in_block ASC
+            "#,
+            block.0 as i64
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        let mut event_hashes = Vec::with_capacity(rows.len() * 32);
+        for row in rows {
+            let topics = [row.topic1, row.topic2, row.topic3, row.topic4];
+            let event = VmEvent {
+                // The location is not covered by the content hash.
+                location: (L1BatchNumber(0), 0),
+                address: Address::from_slice(&row.address),
+                indexed_topics: topics
+                    .into_iter()
+                    .filter(|topic| !topic.is_empty())
+                    .map(|topic| H256::from_slice(&topic))
+                    .collect(),
+                value: row.value,
+            };
+            event_hashes.extend_from_slice(event.content_hash().as_bytes());
+        }
+        Ok(Some(H256(keccak256(&event_hashes))))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use zksync_types::{Address, H256};
+    use zksync_types::U64;
 
     use super::*;
-    use crate::connection::ConnectionPool;
+    use crate::{
+        connection::ConnectionPool,
+        tests::{create_miniblock_header, insert_miniblocks, save_tx_events},
+    };
+
+    #[tokio::test]
+    async fn resolving_web3_block_id() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        let miniblock = create_miniblock_header(1);
+        insert_miniblocks(&mut conn, [miniblock.clone()]).await;
+
+        let resolved = conn
+            .events_web3_dal()
+            .resolve_block_id(BlockId::Number(BlockNumber::Number(3.into())))
+            .await
+            .unwrap();
+        assert_eq!(resolved, Some(MiniblockNumber(3)));
+        let too_large_number = U64::from(u64::from(u32::MAX) + 1);
+        let resolved = conn
+            .events_web3_dal()
+            .resolve_block_id(BlockId::Number(BlockNumber::Number(too_large_number)))
+            .await
+            .unwrap();
+        assert_eq!(resolved, None);
+
+        let resolved = conn
+            .events_web3_dal()
+            .resolve_block_id(BlockId::Hash(miniblock.hash))
+            .await
+            .unwrap();
+        assert_eq!(resolved, Some(MiniblockNumber(1)));
+        let resolved = conn
+            .events_web3_dal()
+            .resolve_block_id(BlockId::Hash(H256::repeat_byte(0xff)))
+            .await
+            .unwrap();
+        assert_eq!(resolved, None);
+
+        let resolved = conn
+            .events_web3_dal()
+            .resolve_block_id(BlockId::Number(BlockNumber::Latest))
+            .await
+            .unwrap();
+        assert_eq!(resolved, Some(MiniblockNumber(1)));
+    }
+
+    #[tokio::test]
+    async fn computing_block_events_digest() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, (1..=2).map(create_miniblock_header)).await;
+
+        let events: Vec<_> = (0_u8..3)
+            .map(|i| VmEvent {
+                location: (L1BatchNumber(1), u32::from(i)),
+                address: Address::repeat_byte(i),
+                indexed_topics: (0..i).map(H256::repeat_byte).collect(),
+                value: vec![i; 10],
+            })
+            .collect();
+        save_tx_events(&mut conn, 1, &events).await;
+
+        let digest = conn
+            .events_web3_dal()
+            .block_events_digest(MiniblockNumber(1))
+            .await
+            .unwrap()
+            .expect("no digest");
+        let content_hashes: Vec<_> = events
+            .iter()
+            .flat_map(|event| event.content_hash().0)
+            .collect();
+        assert_eq!(digest, H256(keccak256(&content_hashes)));
+        let same_digest = conn
+            .events_web3_dal()
+            .block_events_digest(MiniblockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(same_digest, Some(digest));
+        for number in [0, 2, 3] {
+            let digest = conn
+                .events_web3_dal()
+                .block_events_digest(MiniblockNumber(number))
+                .await
+                .unwrap();
+            assert_eq!(digest, None);
+        }
+
+        // Alter the event data.
+        sqlx::query(
+            "UPDATE events SET value = $1 WHERE miniblock_number = 1 AND event_index_in_block = 2",
+        )
+        .bind(vec![3_u8; 10])
+        .execute(conn.conn())
+        .await
+        .unwrap();
+        let altered_digest = conn
+            .events_web3_dal()
+            .block_events_digest(MiniblockNumber(1))
+            .await
+            .unwrap()
+            .expect("no digest");
+        assert_ne!(altered_digest, digest);
+    }
+
+    #[tokio::test]
+    async fn getting_logs_with_system_flag() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, [create_miniblock_header(1)]).await;
+
+        let addresses = [
+            Address::from_low_u64_be(0x800a), // L2 base token
+            Address::repeat_byte(0x11),
+        ];
+        let events: Vec<_> = (0_u32..)
+            .zip(addresses)
+            .map(|(i, address)| VmEvent {
+                location: (L1BatchNumber(1), i),
+                address,
+                indexed_topics: vec![H256::repeat_byte(1)],
+                value: vec![],
+            })
+            .collect();
+        save_tx_events(&mut conn, 1, &events).await;
+
+        let filter = GetLogsFilter::single_block(MiniblockNumber(1));
+        let logs = conn
+            .events_web3_dal()
+            .get_raw_logs_with_system_flag(filter, 10)
+            .await
+            .unwrap();
+        let flags: Vec<_> = logs
+            .iter()
+            .map(|(log, is_system)| (log.address, *is_system))
+            .collect();
+        assert_eq!(flags, [(addresses[0], true), (addresses[1], false)]);
+    }
+
+    #[tokio::test]
+    async fn getting_logs_with_truncation_flag() {
+        const EVENTS_PER_MINIBLOCK: u32 = 4;
+        const LIMIT: usize = 5;
+
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        insert_miniblocks(&mut conn, (1..=3).map(create_miniblock_header)).await;
+        for number in 1..=3 {
+            let events: Vec<_> = (0..EVENTS_PER_MINIBLOCK)
+                .map(|i| VmEvent {
+                    location: (L1BatchNumber(1), i),
+                    address: Address::repeat_byte(0x11),
+                    indexed_topics: vec![H256::repeat_byte(1)],
+                    value: vec![],
+                })
+                .collect();
+            save_tx_events(&mut conn, number, &events).await;
+        }
+
+        let filter = GetLogsFilter::for_miniblock_range(
+            MiniblockNumber(1)..=MiniblockNumber(3),
+            vec![Address::repeat_byte(0x11)],
+            vec![],
+        );
+        let (logs, is_truncated) = conn
+            .events_web3_dal()
+            .get_logs_with_truncation_flag(filter.clone(), LIMIT)
+            .await
+            .unwrap();
+        assert!(is_truncated);
+        assert_eq!(logs.len(), LIMIT);
+
+        let (logs, is_truncated) = conn
+            .events_web3_dal()
+            .get_logs_with_truncation_flag(filter.clone(), 3 * EVENTS_PER_MINIBLOCK as usize)
+            .await
+            .unwrap();
+        assert!(!is_truncated);
+        assert_eq!(logs.len(), 3 * EVENTS_PER_MINIBLOCK as usize);
+
+        // Page through the logs by adjusting `from_block`.
+        let mut all_logs = vec![];
+        let mut page_filter = filter;
+        loop {
+            let (logs, is_truncated) = conn
+                .events_web3_dal()
+                .get_logs_with_truncation_flag(page_filter.clone(), LIMIT)
+                .await
+                .unwrap();
+            let last_seen = all_logs
+                .last()
+                .map(|log: &Log| (log.block_number.unwrap(), log.log_index.unwrap()));
+            all_logs.extend(logs.into_iter().filter(|log| {
+                last_seen.map_or(true, |last_seen| {
+                    (log.block_number.unwrap(), log.log_index.unwrap()) > last_seen
+                })
+            }));
+            if !is_truncated {
+                break;
+            }
+            let last_block = all_logs.last().unwrap().block_number.unwrap();
+            page_filter.from_block = MiniblockNumber(last_block.as_u32()).into();
+        }
+
+        let log_positions: Vec<_> = all_logs
+            .iter()
+            .map(|log| {
+                (
+                    log.block_number.unwrap().as_u32(),
+                    log.log_index.unwrap().as_u32(),
+                )
+            })
+            .collect();
+        let expected_positions: Vec<_> = (1..=3)
+            .flat_map(|number| (0..EVENTS_PER_MINIBLOCK).map(move |i| (number, i)))
+            .collect();
+        assert_eq!(log_positions, expected_positions);
+    }
 
     #[tokio::test]
     async fn test_build_get_logs_where_clause() {
@@ -204,10 +712,11 @@ mod tests {
         let storage = &mut connection_pool.access_storage().await.unwrap();
         let events_web3_dal = EventsWeb3Dal { storage };
         let filter = GetLogsFilter {
-            from_block: MiniblockNumber(100),
-            to_block: MiniblockNumber(200),
+            from_block: MiniblockNumber(100).into(),
+            to_block: MiniblockNumber(200).into(),
             addresses: vec![Address::from_low_u64_be(123)],
             topics: vec![(0, vec![H256::from_low_u64_be(456)])],
+            initiator: None,
         };
 
         let expected_sql = "(miniblock_number >= 100) AND (miniblock_number <= 200) AND (address = ANY($1)) AND (topic0 = ANY($2))";
@@ -217,5 +726,30 @@ mod tests {
 
         assert_eq!(actual_sql, expected_sql);
         assert_eq!(actual_arg_index, expected_arg_index);
+
+        let filter = GetLogsFilter {
+            initiator: Some(Address::from_low_u64_be(789)),
+            ..filter
+        };
+        let expected_sql = "(miniblock_number >= 100) AND (miniblock_number <= 200) AND (address = ANY($1)) AND (topic0 = ANY($2)) AND (tx_initiator_address = $3)";
+
+        let (actual_sql, actual_arg_index) = events_web3_dal.build_get_logs_where_clause(&filter);
+
+        assert_eq!(actual_sql, expected_sql);
+        assert_eq!(actual_arg_index, 4);
+
+        let filter = GetLogsFilter {
+            from_block: LogsFilterBound::Earliest,
+            to_block: LogsFilterBound::Latest,
+            addresses: vec![],
+            topics: vec![],
+            initiator: None,
+        };
+        let expected_sql = "(miniblock_number >= (SELECT MIN(number) FROM miniblocks)) AND (miniblock_number <= (SELECT MAX(number) FROM miniblocks))";
+
+        let (actual_sql, actual_arg_index) = events_web3_dal.build_get_logs_where_clause(&filter);
+
+        assert_eq!(actual_sql, expected_sql);
+        assert_eq!(actual_arg_index, 1);
     }
 }
@@ -27,4 +27,27 @@ impl SystemDal<'_, '_> {
             _ => 0,
         }
     }
+
+    /// Checks whether the specified table in the current schema has the specified column. This can be used
+    /// to check that DB migrations are applied before running code depending on them.
+    pub async fn has_column(&mut self, table: &str, column: &str) -> sqlx::Result<bool> {
+        let count = sqlx::query!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                information_schema.columns
+            WHERE
+                table_schema = CURRENT_SCHEMA()
+                AND table_name = $1::TEXT
+                AND column_name = $2::TEXT
+            "#,
+            table,
+            column
+        )
+        .fetch_one(self.storage.conn())
+        .await?
+        .count;
+        Ok(count > 0)
+    }
 }
@@ -2,16 +2,19 @@ use std::time::Duration;
 
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_types::{
-    block::{MiniblockHasher, MiniblockHeader},
+    block::{L1BatchHeader, MiniblockHasher, MiniblockHeader},
     fee::{Fee, TransactionExecutionMetrics},
     fee_model::BatchFeeInput,
     helpers::unix_timestamp_ms,
     l1::{L1Tx, OpProcessingType, PriorityQueueType},
     l2::L2Tx,
     snapshots::SnapshotRecoveryStatus,
-    tx::{tx_execution_info::TxExecutionStatus, ExecutionMetrics, TransactionExecutionResult},
+    tx::{
+        tx_execution_info::TxExecutionStatus, ExecutionMetrics, IncludedTxLocation,
+        TransactionExecutionResult,
+    },
     Address, Execute, L1BatchNumber, L1BlockNumber, L1TxCommonData, L2ChainId, MiniblockNumber,
-    PriorityOpId, ProtocolVersionId, H160, H256, U256,
+    Nonce, PriorityOpId, ProtocolVersion, ProtocolVersionId, VmEvent, H160, H256, U256,
 };
 
 use crate::{
@@ -20,6 +23,7 @@ use crate::{
     protocol_versions_dal::ProtocolVersionsDal,
     transactions_dal::{L2TxSubmissionResult, TransactionsDal},
     transactions_web3_dal::TransactionsWeb3Dal,
+    StorageProcessor,
 };
 
 const DEFAULT_GAS_PER_PUBDATA: u32 = 100;
@@ -132,6 +136,64 @@ pub(crate) fn create_snapshot_recovery() -> SnapshotRecoveryStatus {
     }
 }
 
+/// Saves the default protocol version and inserts the provided miniblock headers.
+pub(crate) async fn insert_miniblocks(
+    conn: &mut StorageProcessor<'_>,
+    headers: impl IntoIterator<Item = MiniblockHeader>,
+) {
+    conn.protocol_versions_dal()
+        .save_protocol_version_with_tx(ProtocolVersion::default())
+        .await;
+    for header in headers {
+        conn.blocks_dal().insert_miniblock(&header).await.unwrap();
+    }
+}
+
+/// Inserts a mock L1 batch with the specified number and includes all pending miniblocks into it.
+pub(crate) async fn seal_mock_l1_batch(conn: &mut StorageProcessor<'_>, number: u32) {
+    let header = L1BatchHeader::new(
+        L1BatchNumber(number),
+        number.into(),
+        BaseSystemContractsHashes::default(),
+        ProtocolVersionId::latest(),
+    );
+    conn.blocks_dal()
+        .insert_mock_l1_batch(&header)
+        .await
+        .unwrap();
+    conn.blocks_dal()
+        .mark_miniblocks_as_executed_in_l1_batch(header.number)
+        .await
+        .unwrap();
+}
+
+/// Creates a location of a transaction initiated by the zero address.
+pub(crate) fn mock_tx_location(tx_hash: H256, tx_index_in_miniblock: u32) -> IncludedTxLocation {
+    IncludedTxLocation {
+        tx_hash,
+        tx_index_in_miniblock,
+        tx_initiator_address: Address::default(),
+    }
+}
+
+/// Saves `events` as emitted by a single transaction in the specified miniblock. The transaction hash
+/// is derived from the miniblock number; events must belong to L1 batch #1.
+pub(crate) async fn save_tx_events(
+    conn: &mut StorageProcessor<'_>,
+    miniblock_number: u32,
+    events: &[VmEvent],
+) {
+    let location = mock_tx_location(H256::from_low_u64_be(miniblock_number.into()), 0);
+    conn.events_dal()
+        .save_events(
+            MiniblockNumber(miniblock_number),
+            L1BatchNumber(1),
+            &[(location, events.iter().collect())],
+        )
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 async fn workflow_with_submit_tx_equal_hashes() {
     let connection_pool = ConnectionPool::test_pool().await;
@@ -272,3 +334,38 @@ async fn remove_stuck_txs() {
 
     assert_eq!(receipts.len(), 1);
 }
+
+#[tokio::test]
+async fn binding_and_decoding_basic_types() {
+    use sqlx::Row;
+
+    let connection_pool = ConnectionPool::test_pool().await;
+    let mut conn = connection_pool.access_storage().await.unwrap();
+    let row = sqlx::query("SELECT $1 AS miniblock, $2 AS l1_batch, $3 AS l1_block, $4 AS nonce")
+        .bind(MiniblockNumber(42))
+        .bind(L1BatchNumber(u32::MAX))
+        .bind(L1BlockNumber(0))
+        .bind(Nonce(5))
+        .fetch_one(conn.conn())
+        .await
+        .unwrap();
+    assert_eq!(
+        row.get::<MiniblockNumber, _>("miniblock"),
+        MiniblockNumber(42)
+    );
+    assert_eq!(
+        row.get::<L1BatchNumber, _>("l1_batch"),
+        L1BatchNumber(u32::MAX)
+    );
+    assert_eq!(row.get::<L1BlockNumber, _>("l1_block"), L1BlockNumber(0));
+    assert_eq!(row.get::<Nonce, _>("nonce"), Nonce(5));
+
+    let row = sqlx::query("SELECT 4294967296::BIGINT AS too_large, -1::BIGINT AS negative")
+        .fetch_one(conn.conn())
+        .await
+        .unwrap();
+    let err = row.try_get::<MiniblockNumber, _>("too_large").unwrap_err();
+    assert!(err.to_string().contains("out of range"), "{err}");
+    let err = row.try_get::<L1BatchNumber, _>("negative").unwrap_err();
+    assert!(err.to_string().contains("out of range"), "{err}");
+}
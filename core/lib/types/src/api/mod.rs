@@ -1,6 +1,9 @@
+use std::ops::RangeInclusive;
+
 use chrono::{DateTime, Utc};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use strum::Display;
+use thiserror::Error;
 use zksync_basic_types::{
     web3::types::{Bytes, H160, H256, H64, U256, U64},
     L1BatchNumber,
@@ -546,12 +549,263 @@ pub struct TransactionDetails {
     pub eth_execute_tx_hash: Option<H256>,
 }
 
-#[derive(Debug, Clone)]
+/// Bound of the miniblock range in [`GetLogsFilter`]. Symbolic bounds are resolved by the storage
+/// when logs are queried, so that they cannot become stale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogsFilterBound {
+    /// Specific miniblock.
+    Number(MiniblockNumber),
+    /// The latest sealed miniblock.
+    Latest,
+    /// The earliest miniblock present in the storage.
+    Earliest,
+}
+
+impl From<MiniblockNumber> for LogsFilterBound {
+    fn from(number: MiniblockNumber) -> Self {
+        Self::Number(number)
+    }
+}
+
+impl LogsFilterBound {
+    /// Checks whether this bound is guaranteed to not exceed `other` regardless of the storage state.
+    fn is_le(self, other: Self) -> bool {
+        match (self, other) {
+            (Self::Earliest, _) | (_, Self::Latest) => true,
+            (Self::Number(number), Self::Number(other_number)) => number <= other_number,
+            (Self::Number(number), Self::Earliest) => number.0 == 0,
+            (Self::Latest, _) => false,
+        }
+    }
+}
+
+/// Error parsing a [`GetLogsFilter`] from query-string parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FilterParseError {
+    #[error("invalid value for `{param}`: {value:?}")]
+    InvalidValue { param: String, value: String },
+    #[error("parameter `{0}` is specified multiple times")]
+    DuplicateParam(String),
+    #[error("unknown parameter `{0}`")]
+    UnknownParam(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct GetLogsFilter {
-    pub from_block: MiniblockNumber,
-    pub to_block: MiniblockNumber,
+    pub from_block: LogsFilterBound,
+    pub to_block: LogsFilterBound,
     pub addresses: Vec<Address>,
     pub topics: Vec<(u32, Vec<H256>)>,
+    /// If set, only logs emitted by transactions initiated by this address are matched.
+    pub initiator: Option<Address>,
+}
+
+impl GetLogsFilter {
+    /// Creates a filter matching all logs in the specified miniblock.
+    pub fn single_block(block: MiniblockNumber) -> Self {
+        Self {
+            from_block: block.into(),
+            to_block: block.into(),
+            addresses: vec![],
+            topics: vec![],
+            initiator: None,
+        }
+    }
+
+    /// Creates a filter matching logs in the specified miniblock range with the specified addresses and topics.
+    pub fn for_miniblock_range(
+        range: RangeInclusive<MiniblockNumber>,
+        addresses: Vec<Address>,
+        topics: Vec<(u32, Vec<H256>)>,
+    ) -> Self {
+        let (start, end) = range.into_inner();
+        debug_assert!(start <= end, "invalid miniblock range: {start}..={end}");
+        Self {
+            from_block: start.into(),
+            to_block: end.into(),
+            addresses,
+            topics,
+            initiator: None,
+        }
+    }
+
+    /// Parses a filter from query-string parameters, such as `?fromBlock=1&toBlock=latest&address=0x..&topic0=0x..`.
+    /// Supported parameters are:
+    ///
+    /// - `fromBlock` / `toBlock`: decimal or hex miniblock number, `latest` or `earliest`; `latest` if not specified
+    /// - `address`: may be repeated to match logs emitted by any of the specified addresses
+    /// - `topic0`..`topic3`: positional topics; may be repeated to match any of the specified topics at the position
+    ///
+    /// Parsing never panics, so it's safe to use with untrusted input.
+    pub fn from_query_pairs(
+        pairs: impl Iterator<Item = (String, String)>,
+    ) -> Result<Self, FilterParseError> {
+        fn parse_bound(param: &str, value: &str) -> Result<LogsFilterBound, FilterParseError> {
+            let (digits, radix) = match value {
+                "latest" => return Ok(LogsFilterBound::Latest),
+                "earliest" => return Ok(LogsFilterBound::Earliest),
+                _ => match value.strip_prefix("0x") {
+                    Some(hex) => (hex, 16),
+                    None => (value, 10),
+                },
+            };
+            // `from_str_radix()` accepts a leading `+`, which we don't want to allow.
+            let is_valid = !digits.is_empty() && digits.chars().all(|ch| ch.is_digit(radix));
+            is_valid
+                .then(|| u32::from_str_radix(digits, radix).ok())
+                .flatten()
+                .map(|number| MiniblockNumber(number).into())
+                .ok_or_else(|| FilterParseError::InvalidValue {
+                    param: param.to_owned(),
+                    value: value.to_owned(),
+                })
+        }
+
+        let mut from_block = None;
+        let mut to_block = None;
+        let mut addresses = vec![];
+        let mut topics: Vec<(u32, Vec<H256>)> = vec![];
+        for (param, value) in pairs {
+            let invalid_value = || FilterParseError::InvalidValue {
+                param: param.clone(),
+                value: value.clone(),
+            };
+
+            match param.as_str() {
+                "fromBlock" | "toBlock" => {
+                    let bound = if param == "fromBlock" {
+                        &mut from_block
+                    } else {
+                        &mut to_block
+                    };
+                    if bound.is_some() {
+                        return Err(FilterParseError::DuplicateParam(param));
+                    }
+                    *bound = Some(parse_bound(&param, &value)?);
+                }
+                "address" => {
+                    addresses.push(value.parse::<Address>().map_err(|_| invalid_value())?);
+                }
+                _ => {
+                    // Internal topic positions are 1-based.
+                    let position = match param.as_str() {
+                        "topic0" => 1,
+                        "topic1" => 2,
+                        "topic2" => 3,
+                        "topic3" => 4,
+                        _ => return Err(FilterParseError::UnknownParam(param)),
+                    };
+                    let topic = value.parse::<H256>().map_err(|_| invalid_value())?;
+                    match topics.iter_mut().find(|(pos, _)| *pos == position) {
+                        Some((_, position_topics)) => position_topics.push(topic),
+                        None => topics.push((position, vec![topic])),
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            from_block: from_block.unwrap_or(LogsFilterBound::Latest),
+            to_block: to_block.unwrap_or(LogsFilterBound::Latest),
+            addresses,
+            topics,
+            initiator: None,
+        })
+    }
+
+    /// Brings the filter to the canonical form without changing the set of matched logs: deduplicates and sorts
+    /// `addresses` and topics for each position, and sorts topic constraints by position. This method is idempotent.
+    pub fn normalize(&mut self) {
+        self.addresses.sort_unstable();
+        self.addresses.dedup();
+
+        for (_, topics) in &mut self.topics {
+            topics.sort_unstable();
+            topics.dedup();
+        }
+        // Constraints for the same position are combined using AND, so identical constraints are redundant.
+        self.topics.sort_unstable();
+        self.topics.dedup();
+    }
+
+    /// Converts this filter into an equivalent web3 filter, e.g. to forward the query to an upstream node.
+    /// Topic constraints are mapped by their 1-based position; several constraints for the same position
+    /// are intersected. Web3 filters cannot express [`Self::initiator`] or contradictory constraints for the same
+    /// topic position, so in these cases the returned filter matches a superset of logs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a topic position is outside `1..=4`.
+    pub fn to_web3_filter(&self) -> crate::web3::types::Filter {
+        use crate::web3::types::{BlockNumber as Web3BlockNumber, FilterBuilder};
+
+        fn convert_bound(bound: LogsFilterBound) -> Web3BlockNumber {
+            match bound {
+                LogsFilterBound::Number(number) => Web3BlockNumber::Number(number.0.into()),
+                LogsFilterBound::Latest => Web3BlockNumber::Latest,
+                LogsFilterBound::Earliest => Web3BlockNumber::Earliest,
+            }
+        }
+
+        let mut topics: [Option<Vec<H256>>; 4] = Default::default();
+        for (position, position_topics) in &self.topics {
+            assert!(
+                (1..=4).contains(position),
+                "topic position {position} is out of range"
+            );
+            let slot = &mut topics[*position as usize - 1];
+            *slot = Some(match slot.take() {
+                None => position_topics.clone(),
+                Some(existing) => existing
+                    .into_iter()
+                    .filter(|topic| position_topics.contains(topic))
+                    .collect(),
+            });
+        }
+        let [topic1, topic2, topic3, topic4] = topics;
+
+        let mut builder = FilterBuilder::default()
+            .from_block(convert_bound(self.from_block))
+            .to_block(convert_bound(self.to_block))
+            .topics(topic1, topic2, topic3, topic4);
+        // An empty address list means that any address is matched, which corresponds to an unset address
+        // in a web3 filter.
+        if !self.addresses.is_empty() {
+            builder = builder.address(self.addresses.clone());
+        }
+        builder.build()
+    }
+
+    /// Checks whether logs matching `other` are guaranteed to be a subset of logs matching this filter.
+    /// The check is conservative: `false` may be returned even if the condition holds.
+    pub fn subsumes(&self, other: &GetLogsFilter) -> bool {
+        if !self.from_block.is_le(other.from_block) || !other.to_block.is_le(self.to_block) {
+            return false;
+        }
+
+        // Empty `addresses` means that logs from any address are matched.
+        let addresses_subsumed = self.addresses.is_empty()
+            || (!other.addresses.is_empty()
+                && other
+                    .addresses
+                    .iter()
+                    .all(|address| self.addresses.contains(address)));
+        if !addresses_subsumed {
+            return false;
+        }
+        if self.initiator.is_some() && self.initiator != other.initiator {
+            return false;
+        }
+
+        // Each topic constraint in this filter must be implied by a constraint in `other`
+        // for the same topic position.
+        self.topics.iter().all(|(position, topics)| {
+            other.topics.iter().any(|(other_position, other_topics)| {
+                other_position == position
+                    && other_topics.iter().all(|topic| topics.contains(topic))
+            })
+        })
+    }
 }
 
 /// Result of debugging block
@@ -702,3 +956,255 @@ pub struct Proof {
     pub address: Address,
     pub storage_proof: Vec<StorageProof>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_filter(
+        blocks: (u32, u32),
+        addresses: &[u8],
+        topics: &[(u32, &[u8])],
+    ) -> GetLogsFilter {
+        GetLogsFilter::for_miniblock_range(
+            MiniblockNumber(blocks.0)..=MiniblockNumber(blocks.1),
+            addresses
+                .iter()
+                .copied()
+                .map(Address::repeat_byte)
+                .collect(),
+            topics
+                .iter()
+                .map(|(position, topics)| {
+                    let topics = topics.iter().copied().map(H256::repeat_byte).collect();
+                    (*position, topics)
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn filter_subsumes_narrower_filter() {
+        let wide = create_filter((0, 100), &[], &[]);
+        let narrow = create_filter((10, 20), &[1], &[(1, &[2])]);
+        assert!(wide.subsumes(&narrow));
+        assert!(!narrow.subsumes(&wide));
+        assert!(narrow.subsumes(&narrow));
+
+        let wide = create_filter((0, 100), &[1, 2], &[(1, &[2, 3])]);
+        assert!(wide.subsumes(&narrow));
+        let narrow = create_filter((10, 20), &[2], &[(1, &[3]), (2, &[4])]);
+        assert!(wide.subsumes(&narrow));
+    }
+
+    #[test]
+    fn filter_does_not_subsume_disjoint_filter() {
+        let filter = create_filter((0, 10), &[1], &[]);
+        let other = create_filter((20, 30), &[1], &[]);
+        assert!(!filter.subsumes(&other));
+        assert!(!other.subsumes(&filter));
+
+        let other = create_filter((0, 10), &[2], &[]);
+        assert!(!filter.subsumes(&other));
+        assert!(!other.subsumes(&filter));
+
+        let filter = create_filter((0, 10), &[], &[(1, &[1])]);
+        let other = create_filter((0, 10), &[], &[(1, &[2])]);
+        assert!(!filter.subsumes(&other));
+        assert!(!other.subsumes(&filter));
+    }
+
+    #[test]
+    fn filter_subsumption_with_symbolic_bounds() {
+        let filter = create_filter((10, 20), &[1], &[]);
+        let unbounded = GetLogsFilter {
+            from_block: LogsFilterBound::Earliest,
+            to_block: LogsFilterBound::Latest,
+            ..filter.clone()
+        };
+        assert!(unbounded.subsumes(&filter));
+        assert!(!filter.subsumes(&unbounded));
+        assert!(unbounded.subsumes(&unbounded));
+
+        let from_genesis = create_filter((0, 20), &[1], &[]);
+        assert!(from_genesis.subsumes(&GetLogsFilter {
+            from_block: LogsFilterBound::Earliest,
+            ..filter.clone()
+        }));
+
+        // The latest miniblock is not known in advance, so it cannot be compared to a specific number.
+        let latest = GetLogsFilter {
+            from_block: LogsFilterBound::Latest,
+            to_block: LogsFilterBound::Latest,
+            ..filter.clone()
+        };
+        assert!(!filter.subsumes(&latest));
+        assert!(!latest.subsumes(&filter));
+        assert!(unbounded.subsumes(&latest));
+    }
+
+    #[test]
+    fn filter_subsumption_with_initiator() {
+        let filter = create_filter((0, 10), &[1], &[]);
+        let other = GetLogsFilter {
+            initiator: Some(Address::repeat_byte(2)),
+            ..filter.clone()
+        };
+        assert!(filter.subsumes(&other));
+        assert!(!other.subsumes(&filter));
+        assert!(other.subsumes(&other));
+
+        let another = GetLogsFilter {
+            initiator: Some(Address::repeat_byte(3)),
+            ..filter
+        };
+        assert!(!other.subsumes(&another));
+    }
+
+    #[test]
+    fn filter_subsumption_is_conservative() {
+        // Constraints on different topic positions cannot be compared.
+        let filter = create_filter((0, 10), &[], &[(1, &[1])]);
+        let other = create_filter((0, 10), &[], &[(2, &[1])]);
+        assert!(!filter.subsumes(&other));
+        assert!(!other.subsumes(&filter));
+
+        // Partially overlapping address sets.
+        let filter = create_filter((0, 10), &[1, 2], &[]);
+        let other = create_filter((0, 10), &[2, 3], &[]);
+        assert!(!filter.subsumes(&other));
+        assert!(!other.subsumes(&filter));
+    }
+
+    #[test]
+    fn normalizing_filter() {
+        let mut filter = create_filter((0, 10), &[3, 1, 3, 2], &[(2, &[5, 4]), (1, &[1, 1])]);
+        filter.normalize();
+        assert_eq!(
+            filter,
+            create_filter((0, 10), &[1, 2, 3], &[(1, &[1]), (2, &[4, 5])])
+        );
+
+        let normalized = filter.clone();
+        filter.normalize();
+        assert_eq!(filter, normalized);
+
+        let mut permuted = create_filter(
+            (0, 10),
+            &[2, 1, 3],
+            &[(2, &[4, 5, 4]), (1, &[1]), (2, &[5, 4])],
+        );
+        permuted.normalize();
+        assert_eq!(permuted, normalized);
+    }
+
+    fn query_pairs<'a>(pairs: &'a [(&str, &str)]) -> impl Iterator<Item = (String, String)> + 'a {
+        pairs
+            .iter()
+            .map(|&(param, value)| (param.to_owned(), value.to_owned()))
+    }
+
+    #[test]
+    fn parsing_filter_from_query_pairs() {
+        let first_address = format!("{:?}", Address::repeat_byte(1));
+        let second_address = format!("{:?}", Address::repeat_byte(2));
+        let first_topic = format!("{:?}", H256::repeat_byte(1));
+        let second_topic = format!("{:?}", H256::repeat_byte(2));
+        let pairs = [
+            ("fromBlock", "3"),
+            ("toBlock", "0xa"),
+            ("address", first_address.as_str()),
+            ("topic2", first_topic.as_str()),
+            ("address", second_address.as_str()),
+            ("topic0", second_topic.as_str()),
+            ("topic2", second_topic.as_str()),
+        ];
+        let filter = GetLogsFilter::from_query_pairs(query_pairs(&pairs)).unwrap();
+        assert_eq!(
+            filter,
+            create_filter((3, 10), &[1, 2], &[(3, &[1, 2]), (1, &[2])])
+        );
+
+        let filter =
+            GetLogsFilter::from_query_pairs(query_pairs(&[("fromBlock", "earliest")])).unwrap();
+        assert_eq!(filter.from_block, LogsFilterBound::Earliest);
+        assert_eq!(filter.to_block, LogsFilterBound::Latest);
+        assert!(filter.addresses.is_empty() && filter.topics.is_empty());
+    }
+
+    #[test]
+    fn parsing_malformed_filter_from_query_pairs() {
+        let malformed_bounds = ["", "-1", "0x", "0xzz", "4294967296", "1.5", "pending", "+1"];
+        for bound in malformed_bounds {
+            let err =
+                GetLogsFilter::from_query_pairs(query_pairs(&[("toBlock", bound)])).unwrap_err();
+            assert_eq!(
+                err,
+                FilterParseError::InvalidValue {
+                    param: "toBlock".to_owned(),
+                    value: bound.to_owned(),
+                }
+            );
+        }
+
+        let err =
+            GetLogsFilter::from_query_pairs(query_pairs(&[("fromBlock", "1"), ("fromBlock", "2")]))
+                .unwrap_err();
+        assert_eq!(
+            err,
+            FilterParseError::DuplicateParam("fromBlock".to_owned())
+        );
+
+        for param in ["topic4", "topic", "topic+1", "topics", "blockHash"] {
+            let err = GetLogsFilter::from_query_pairs(query_pairs(&[(param, "0x00")])).unwrap_err();
+            assert_eq!(err, FilterParseError::UnknownParam(param.to_owned()));
+        }
+
+        let err =
+            GetLogsFilter::from_query_pairs(query_pairs(&[("address", "0x1234")])).unwrap_err();
+        assert!(
+            matches!(err, FilterParseError::InvalidValue { .. }),
+            "{err}"
+        );
+        let err = GetLogsFilter::from_query_pairs(query_pairs(&[("topic1", "what")])).unwrap_err();
+        assert!(
+            matches!(err, FilterParseError::InvalidValue { .. }),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn converting_filter_to_web3() {
+        let filter = create_filter((3, 10), &[1, 2], &[(1, &[1, 2]), (3, &[3])]);
+        let web3_filter = serde_json::to_value(filter.to_web3_filter()).unwrap();
+        assert_eq!(
+            web3_filter,
+            serde_json::json!({
+                "fromBlock": "0x3",
+                "toBlock": "0xa",
+                "address": [Address::repeat_byte(1), Address::repeat_byte(2)],
+                // The wildcard for the 2nd position is preserved, and the trailing wildcard is omitted.
+                "topics": [
+                    [H256::repeat_byte(1), H256::repeat_byte(2)],
+                    null,
+                    H256::repeat_byte(3),
+                ],
+            })
+        );
+
+        let filter = GetLogsFilter {
+            from_block: LogsFilterBound::Earliest,
+            to_block: LogsFilterBound::Latest,
+            ..create_filter((0, 0), &[], &[(2, &[1, 2]), (2, &[2, 3])])
+        };
+        let web3_filter = serde_json::to_value(filter.to_web3_filter()).unwrap();
+        assert_eq!(
+            web3_filter,
+            serde_json::json!({
+                "fromBlock": "earliest",
+                "toBlock": "latest",
+                "topics": [null, H256::repeat_byte(2)],
+            })
+        );
+    }
+}
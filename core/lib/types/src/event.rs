@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{collections::BTreeMap, fmt::Debug};
 
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
@@ -9,6 +9,7 @@ use crate::{
     ethabi,
     l2_to_l1_log::L2ToL1Log,
     tokens::{TokenInfo, TokenMetadata},
+    web3::signing::keccak256,
     Address, L1BatchNumber, CONTRACT_DEPLOYER_ADDRESS, H256, KNOWN_CODES_STORAGE_ADDRESS,
     L1_MESSENGER_ADDRESS, U256,
 };
@@ -21,7 +22,33 @@ pub struct VmEvent {
     pub value: Vec<u8>,
 }
 
+/// Location of a [`VmEvent`]. This is a named equivalent of the [`VmEvent::location`] tuple.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct EventLocation {
+    /// L1 batch the event was emitted in.
+    pub batch: L1BatchNumber,
+    /// Index of the transaction that emitted the event in the L1 batch.
+    pub index: u32,
+}
+
+impl From<(L1BatchNumber, u32)> for EventLocation {
+    fn from((batch, index): (L1BatchNumber, u32)) -> Self {
+        Self { batch, index }
+    }
+}
+
+impl From<EventLocation> for (L1BatchNumber, u32) {
+    fn from(location: EventLocation) -> Self {
+        (location.batch, location.index)
+    }
+}
+
 impl VmEvent {
+    /// Returns the location of this event.
+    pub fn location(&self) -> EventLocation {
+        self.location.into()
+    }
+
     pub fn index_keys(&self) -> impl Iterator<Item = VmEventGroupKey> + '_ {
         self.indexed_topics
             .iter()
@@ -31,6 +58,45 @@ impl VmEvent {
                 topic: (idx as u32, topic),
             })
     }
+
+    /// Returns a hash of the event contents (address, topics and value). The event location is not hashed,
+    /// so logically identical events placed at different locations have the same hash.
+    pub fn content_hash(&self) -> H256 {
+        let mut preimage =
+            Vec::with_capacity(21 + 32 * self.indexed_topics.len() + self.value.len());
+        preimage.extend_from_slice(self.address.as_bytes());
+        // The number of topics is hashed so that topic and value bytes cannot be confused.
+        preimage.push(self.indexed_topics.len() as u8);
+        for topic in &self.indexed_topics {
+            preimage.extend_from_slice(topic.as_bytes());
+        }
+        preimage.extend_from_slice(&self.value);
+        H256(keccak256(&preimage))
+    }
+
+    /// Checks whether the event value is empty or consists of zero bytes only.
+    pub fn has_empty_value(&self) -> bool {
+        self.value.iter().all(|&byte| byte == 0)
+    }
+
+    /// Checks whether the event has duplicate indexed topics. Duplicate topics are legal, but may be suspicious
+    /// for some consumers. Uses a quadratic scan since events have at most 4 topics.
+    pub fn has_duplicate_topics(&self) -> bool {
+        let topics = &self.indexed_topics;
+        topics
+            .iter()
+            .enumerate()
+            .any(|(i, topic)| topics[i + 1..].contains(topic))
+    }
+
+    /// Groups events by the L1 batch they belong to, preserving the order of events within each batch.
+    pub fn group_by_batch(events: &[Self]) -> BTreeMap<L1BatchNumber, Vec<&Self>> {
+        let mut grouped = BTreeMap::<_, Vec<_>>::new();
+        for event in events {
+            grouped.entry(event.location.0).or_default().push(event);
+        }
+        grouped
+    }
 }
 
 pub static DEPLOY_EVENT_SIGNATURE: Lazy<H256> = Lazy::new(|| {
@@ -44,6 +110,18 @@ pub static DEPLOY_EVENT_SIGNATURE: Lazy<H256> = Lazy::new(|| {
     )
 });
 
+/// Signature of the ERC-20 `Transfer` event, which is also emitted by the L2 ETH token on ETH transfers.
+pub static TRANSFER_EVENT_TOPIC: Lazy<H256> = Lazy::new(|| {
+    ethabi::long_signature(
+        "Transfer",
+        &[
+            ethabi::ParamType::Address,
+            ethabi::ParamType::Address,
+            ethabi::ParamType::Uint(256),
+        ],
+    )
+});
+
 static L1_MESSAGE_EVENT_SIGNATURE: Lazy<H256> = Lazy::new(|| {
     ethabi::long_signature(
         "L1MessageSent",
@@ -343,7 +421,7 @@ pub struct VmEventGroupKey {
 mod tests {
     use zksync_basic_types::{
         ethabi::{self, Token},
-        Address, L1BatchNumber, U256,
+        Address, L1BatchNumber, H256, U256,
     };
     use zksync_system_constants::{
         BOOTLOADER_ADDRESS, KNOWN_CODES_STORAGE_ADDRESS, L1_MESSENGER_ADDRESS, L2_ETH_TOKEN_ADDRESS,
@@ -352,7 +430,7 @@ mod tests {
 
     use super::{
         extract_bytecode_publication_requests_from_l1_messenger,
-        extract_l2tol1logs_from_l1_messenger, L1MessengerBytecodePublicationRequest,
+        extract_l2tol1logs_from_l1_messenger, EventLocation, L1MessengerBytecodePublicationRequest,
         L1MessengerL2ToL1Log,
     };
     use crate::VmEvent;
@@ -502,4 +580,140 @@ mod tests {
 
         assert_eq!(expected, logs);
     }
+
+    #[test]
+    fn event_content_hash() {
+        let event = VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: Address::repeat_byte(1),
+            indexed_topics: vec![H256::repeat_byte(2), H256::repeat_byte(3)],
+            value: vec![4, 5, 6],
+        };
+        let relocated_event = VmEvent {
+            location: (L1BatchNumber(2), 10),
+            ..event.clone()
+        };
+        assert_eq!(event.content_hash(), relocated_event.content_hash());
+
+        let other_event = VmEvent {
+            value: vec![4, 5],
+            ..event.clone()
+        };
+        assert_ne!(event.content_hash(), other_event.content_hash());
+        let other_event = VmEvent {
+            indexed_topics: vec![H256::repeat_byte(2)],
+            ..event.clone()
+        };
+        assert_ne!(event.content_hash(), other_event.content_hash());
+        let other_event = VmEvent {
+            address: Address::repeat_byte(2),
+            ..event.clone()
+        };
+        assert_ne!(event.content_hash(), other_event.content_hash());
+    }
+
+    #[test]
+    fn grouping_events_by_batch() {
+        let events: Vec<_> = [(2, 0), (1, 0), (2, 1), (1, 1), (2, 2)]
+            .iter()
+            .map(|&(l1_batch, index)| VmEvent {
+                location: (L1BatchNumber(l1_batch), index),
+                ..VmEvent::default()
+            })
+            .collect();
+
+        let grouped = VmEvent::group_by_batch(&events);
+        let batches: Vec<_> = grouped.keys().copied().collect();
+        assert_eq!(batches, [L1BatchNumber(1), L1BatchNumber(2)]);
+        let locations: Vec<_> = grouped[&L1BatchNumber(1)]
+            .iter()
+            .map(|event| event.location.1)
+            .collect();
+        assert_eq!(locations, [0, 1]);
+        let locations: Vec<_> = grouped[&L1BatchNumber(2)]
+            .iter()
+            .map(|event| event.location.1)
+            .collect();
+        assert_eq!(locations, [0, 1, 2]);
+
+        assert!(VmEvent::group_by_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn checking_empty_event_value() {
+        let event = VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: Address::repeat_byte(1),
+            indexed_topics: vec![H256::repeat_byte(2)],
+            value: vec![],
+        };
+        assert!(event.has_empty_value());
+
+        let event = VmEvent {
+            value: vec![0; 32],
+            ..event
+        };
+        assert!(event.has_empty_value());
+
+        let event = VmEvent {
+            value: vec![0, 0, 1, 0],
+            ..event
+        };
+        assert!(!event.has_empty_value());
+    }
+
+    #[test]
+    fn checking_duplicate_event_topics() {
+        let event = VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: Address::repeat_byte(1),
+            indexed_topics: vec![],
+            value: vec![],
+        };
+        assert!(!event.has_duplicate_topics());
+
+        let event = VmEvent {
+            indexed_topics: (1..=4).map(H256::repeat_byte).collect(),
+            ..event
+        };
+        assert!(!event.has_duplicate_topics());
+
+        let event = VmEvent {
+            indexed_topics: vec![
+                H256::repeat_byte(1),
+                H256::repeat_byte(2),
+                H256::repeat_byte(3),
+                H256::repeat_byte(1),
+            ],
+            ..event
+        };
+        assert!(event.has_duplicate_topics());
+
+        let event = VmEvent {
+            indexed_topics: vec![H256::zero(), H256::zero()],
+            ..event
+        };
+        assert!(event.has_duplicate_topics());
+    }
+
+    #[test]
+    fn converting_event_location() {
+        let tuple = (L1BatchNumber(3), 5);
+        let location = EventLocation::from(tuple);
+        assert_eq!(
+            location,
+            EventLocation {
+                batch: L1BatchNumber(3),
+                index: 5
+            }
+        );
+        assert_eq!(<(L1BatchNumber, u32)>::from(location), tuple);
+
+        let event = VmEvent {
+            location: tuple,
+            ..VmEvent::default()
+        };
+        assert_eq!(event.location(), location);
+        assert_eq!(event.location().batch, event.location.0);
+    }
 }
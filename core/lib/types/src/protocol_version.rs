@@ -1,4 +1,7 @@
-use std::convert::{TryFrom, TryInto};
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt,
+};
 
 use num_enum::TryFromPrimitive;
 use serde::{Deserialize, Serialize};
@@ -100,6 +103,23 @@ impl ProtocolVersionId {
     pub fn is_post_1_4_1(&self) -> bool {
         self >= &ProtocolVersionId::Version20
     }
+
+    /// Creates a protocol version from its semantic version components. All protocol versions
+    /// have the zero major component; the minor component is the version ID.
+    pub fn from_parts(major: u16, minor: u16) -> Result<Self, String> {
+        if major != 0 {
+            return Err(format!("unsupported major protocol version: {major}"));
+        }
+        minor
+            .try_into()
+            .map_err(|_| format!("unknown protocol version ID: {minor}"))
+    }
+}
+
+impl fmt::Display for ProtocolVersionId {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "v0.{}", *self as u16)
+    }
 }
 
 impl Default for ProtocolVersionId {
@@ -723,6 +743,17 @@ impl From<ProtocolVersionId> for VmVersion {
 mod tests {
     use super::*;
 
+    #[test]
+    fn protocol_version_id_from_parts() {
+        let version = ProtocolVersionId::from_parts(0, 20).unwrap();
+        assert_eq!(version, ProtocolVersionId::Version20);
+        assert_eq!(version.to_string(), "v0.20");
+        assert_eq!(ProtocolVersionId::Version0.to_string(), "v0.0");
+
+        ProtocolVersionId::from_parts(1, 20).unwrap_err();
+        ProtocolVersionId::from_parts(0, 1_000).unwrap_err();
+    }
+
     #[test]
     fn governance_operation_from_log() {
         let call_token = Token::Tuple(vec![
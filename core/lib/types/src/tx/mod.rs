@@ -11,7 +11,7 @@ use zksync_utils::bytecode::CompressedBytecodeInfo;
 
 use self::tx_execution_info::TxExecutionStatus;
 pub use self::{execute::Execute, tx_execution_info::ExecutionMetrics};
-use crate::{vm_trace::Call, Transaction};
+use crate::{api::TransactionReceipt, vm_trace::Call, Transaction};
 
 pub mod execute;
 pub mod primitives;
@@ -54,3 +54,43 @@ pub struct IncludedTxLocation {
     pub tx_index_in_miniblock: u32,
     pub tx_initiator_address: Address,
 }
+
+impl IncludedTxLocation {
+    /// Creates a location from a web3 transaction receipt. Returns `None` if the receipt doesn't specify
+    /// the transaction sender (i.e., [`TransactionReceipt::from`] is set to the default value).
+    pub fn from_receipt(receipt: &TransactionReceipt, tx_index_in_miniblock: u32) -> Option<Self> {
+        if receipt.from == Address::zero() {
+            return None;
+        }
+        Some(Self {
+            tx_hash: receipt.transaction_hash,
+            tx_index_in_miniblock,
+            tx_initiator_address: receipt.from,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn included_tx_location_from_receipt() {
+        let receipt = TransactionReceipt {
+            transaction_hash: H256::repeat_byte(1),
+            from: Address::repeat_byte(2),
+            to: Some(Address::repeat_byte(3)),
+            ..TransactionReceipt::default()
+        };
+        let location = IncludedTxLocation::from_receipt(&receipt, 3).unwrap();
+        assert_eq!(location.tx_hash, H256::repeat_byte(1));
+        assert_eq!(location.tx_index_in_miniblock, 3);
+        assert_eq!(location.tx_initiator_address, Address::repeat_byte(2));
+
+        let receipt = TransactionReceipt {
+            from: Address::zero(),
+            ..receipt
+        };
+        assert!(IncludedTxLocation::from_receipt(&receipt, 3).is_none());
+    }
+}
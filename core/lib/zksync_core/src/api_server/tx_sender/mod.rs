@@ -20,8 +20,8 @@ use zksync_types::{
     l1::is_l1_tx_type,
     l2::{error::TxCheckError::TxDuplication, L2Tx},
     utils::storage_key_for_eth_balance,
-    AccountTreeId, Address, ExecuteTransactionCommon, L2ChainId, MiniblockNumber, Nonce,
-    PackedEthSignature, ProtocolVersionId, Transaction, VmVersion, H160, H256, MAX_L2_TX_GAS_LIMIT,
+    AccountTreeId, Address, ExecuteTransactionCommon, L2ChainId, Nonce, PackedEthSignature,
+    ProtocolVersionId, Transaction, VmVersion, H160, H256, MAX_L2_TX_GAS_LIMIT,
     MAX_NEW_FACTORY_DEPS, U256,
 };
 use zksync_utils::h256_to_u256;
@@ -525,7 +525,7 @@ impl TxSender {
             None => {
                 // We don't have miniblocks in the storage yet. Use the snapshot miniblock number instead.
                 let start = BlockStartInfo::new(&mut storage).await?;
-                MiniblockNumber(start.first_miniblock.saturating_sub(1))
+                start.first_miniblock.saturating_sub(1)
             }
         };
 
@@ -864,12 +864,14 @@ impl EthNamespace {
                     );
                 }
 
-                let get_logs_filter = GetLogsFilter {
-                    from_block: *from_block,
-                    to_block,
+                let mut get_logs_filter = GetLogsFilter {
+                    from_block: (*from_block).into(),
+                    to_block: to_block.into(),
                     addresses,
                     topics,
+                    initiator: None,
                 };
+                get_logs_filter.normalize();
 
                 let mut storage = self
                     .state
@@ -141,7 +141,7 @@ impl ZksNamespace {
 
     #[tracing::instrument(skip(self))]
     pub fn l1_chain_id_impl(&self) -> U64 {
-        U64::from(*self.state.api_config.l1_chain_id)
+        self.state.api_config.l1_chain_id.into()
     }
 
     #[tracing::instrument(skip(self))]
@@ -287,12 +287,11 @@ impl ZksNamespace {
             let logs = storage
                 .events_web3_dal()
                 .get_logs(
-                    GetLogsFilter {
-                        from_block: first_miniblock_of_l1_batch,
-                        to_block: block_number,
-                        addresses: vec![L1_MESSENGER_ADDRESS],
-                        topics: vec![(2, vec![address_to_h256(&sender)]), (3, vec![msg])],
-                    },
+                    GetLogsFilter::for_miniblock_range(
+                        first_miniblock_of_l1_batch..=block_number,
+                        vec![L1_MESSENGER_ADDRESS],
+                        vec![(2, vec![address_to_h256(&sender)]), (3, vec![msg])],
+                    ),
                     self.state.api_config.req_entities_limit,
                 )
                 .await
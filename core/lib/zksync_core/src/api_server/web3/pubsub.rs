@@ -73,7 +73,7 @@ impl PubSubNotifier {
             None => {
                 // We don't have miniblocks in the storage yet. Use the snapshot miniblock number instead.
                 let start_info = BlockStartInfo::new(&mut storage).await?;
-                MiniblockNumber(start_info.first_miniblock.saturating_sub(1))
+                start_info.first_miniblock.saturating_sub(1)
             }
         })
     }
@@ -409,9 +409,10 @@ async fn store_events(
         .events_dal()
         .save_events(
             MiniblockNumber(miniblock_number),
+            l1_batch_number,
             &[(tx_location, events.iter().collect())],
         )
-        .await;
+        .await?;
     Ok((tx_location, events))
 }
 
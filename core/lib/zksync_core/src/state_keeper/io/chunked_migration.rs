@@ -0,0 +1,647 @@
+//! Generic runner for data migrations (backfills) processing miniblocks in chunks.
+
+use std::{
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, StorageProcessor};
+use zksync_types::MiniblockNumber;
+
+/// Migration logic for a single chunk of miniblocks, driven by [`ChunkedMigration`].
+#[async_trait]
+pub(crate) trait MigrateChunk: Send {
+    /// Adjusts the end of the next chunk, e.g. to align chunks with L1 batches. The returned value must be
+    /// in `chunk_end..=last_miniblock`. By default, `chunk_end` is returned as is.
+    async fn adjust_chunk_end(
+        &mut self,
+        _storage: &mut StorageProcessor<'_>,
+        chunk_end: MiniblockNumber,
+        _last_miniblock: MiniblockNumber,
+    ) -> anyhow::Result<MiniblockNumber> {
+        Ok(chunk_end)
+    }
+
+    /// Checks whether the chunk is already migrated. Migrated chunks are skipped, which makes the migration idempotent.
+    async fn is_chunk_migrated(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        chunk: RangeInclusive<MiniblockNumber>,
+    ) -> anyhow::Result<bool>;
+
+    /// Migrates the chunk, returning the number of affected rows. Only called for chunks that are not migrated.
+    async fn migrate_chunk(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        chunk: RangeInclusive<MiniblockNumber>,
+    ) -> anyhow::Result<u64>;
+
+    /// Called after each processed chunk. Returns `true` if the migration should stop (e.g., because
+    /// a cap on affected rows is reached). By default, never requests to stop.
+    fn after_chunk(&mut self, _outcome: &ChunkOutcome) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+}
+
+/// Information about a chunk processed by [`ChunkedMigration`].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ChunkOutcome {
+    pub range: RangeInclusive<MiniblockNumber>,
+    /// Whether the chunk was already migrated (in which case, it was skipped).
+    pub was_migrated: bool,
+    pub rows_affected: u64,
+    pub elapsed: Duration,
+}
+
+/// Output of [`ChunkedMigration::run()`].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ChunkedMigrationOutput {
+    pub rows_affected: u64,
+    /// First unprocessed miniblock if the migration was stopped by a stop signal or by [`MigrateChunk::after_chunk()`].
+    /// Exceeds the end of the migrated range if the migration was stopped after the last chunk. `None` if the entire
+    /// range was processed without a stop request.
+    pub stopped_at: Option<MiniblockNumber>,
+}
+
+/// Determines how [`ChunkedMigration`] reacts to the stop signal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum StopMode {
+    /// Finish migrating the current chunk, then stop.
+    #[default]
+    AfterChunk,
+    /// In addition to checks after each chunk, check the stop signal right before migrating a chunk,
+    /// so that no new DB writes are started once the signal is received.
+    BeforeWrite,
+}
+
+/// Parameters for adapting the chunk size of [`ChunkedMigration`] to the chunk processing time.
+///
+/// The chunk size is halved if a chunk took longer than `target_duration` to migrate, and is grown by
+/// 1.5x if a chunk took less than a half of `target_duration`. The chunk size always stays within
+/// `min_chunk_size..=max_chunk_size`. Only the size of the following chunks is changed, so each chunk
+/// is still migrated atomically.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct AdaptiveChunkSize {
+    pub target_duration: Duration,
+    pub min_chunk_size: u32,
+    pub max_chunk_size: u32,
+}
+
+impl AdaptiveChunkSize {
+    fn validate(&self) -> anyhow::Result<()> {
+        anyhow::ensure!(self.min_chunk_size > 0, "Min chunk size must be positive");
+        anyhow::ensure!(
+            self.min_chunk_size <= self.max_chunk_size,
+            "Min chunk size ({}) exceeds max chunk size ({})",
+            self.min_chunk_size,
+            self.max_chunk_size
+        );
+        Ok(())
+    }
+
+    /// Returns the size of the next chunk based on the processing time of the previous chunk.
+    fn next_chunk_size(&self, chunk_size: u32, elapsed: Duration) -> u32 {
+        let next_chunk_size = if elapsed > self.target_duration {
+            chunk_size / 2
+        } else if elapsed < self.target_duration / 2 {
+            chunk_size.saturating_add((chunk_size / 2).max(1))
+        } else {
+            chunk_size
+        };
+        next_chunk_size.clamp(self.min_chunk_size, self.max_chunk_size)
+    }
+}
+
+/// Delay between chunks used by [`ChunkedMigration`] in order to not overload the DB. The delay is only applied
+/// after chunks that needed migration; there is no delay after already migrated chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BackoffPolicy {
+    /// No delay between chunks.
+    None,
+    /// Fixed delay after each migrated chunk.
+    Fixed(Duration),
+    /// Delay doubling with each consecutive migrated chunk, starting from `base` and capped at `max`.
+    /// The delay is reset once an already migrated chunk is encountered.
+    Exponential { base: Duration, max: Duration },
+}
+
+impl BackoffPolicy {
+    fn validate(&self) -> anyhow::Result<()> {
+        if let Self::Exponential { base, max } = self {
+            anyhow::ensure!(
+                base <= max,
+                "Base backoff delay ({base:?}) exceeds max delay ({max:?})"
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the delay after a migrated chunk given the number of consecutive migrated chunks
+    /// (including the current one; i.e., it's always positive).
+    fn delay(&self, consecutive_migrated_chunks: u32) -> Duration {
+        match *self {
+            Self::None => Duration::ZERO,
+            Self::Fixed(delay) => delay,
+            Self::Exponential { base, max } => {
+                let exponent = consecutive_migrated_chunks.saturating_sub(1);
+                2_u32
+                    .checked_pow(exponent)
+                    .and_then(|multiplier| base.checked_mul(multiplier))
+                    .map_or(max, |delay| delay.min(max))
+            }
+        }
+    }
+}
+
+/// Runner for migrations processing miniblocks in chunks of a fixed size. Each chunk is processed using a separate
+/// storage connection; the runner sleeps between chunks that needed migration according to the [`BackoffPolicy`].
+/// The stop signal is checked after each chunk, and optionally before migrating a chunk (see [`StopMode`]).
+///
+/// Optionally, the chunk size can be [adapted](AdaptiveChunkSize) to the chunk processing time.
+#[derive(Debug)]
+pub(crate) struct ChunkedMigration {
+    pool: ConnectionPool,
+    chunk_size: u32,
+    backoff: BackoffPolicy,
+    adaptive_chunk_size: Option<AdaptiveChunkSize>,
+    stop_mode: StopMode,
+}
+
+impl ChunkedMigration {
+    pub fn new(pool: ConnectionPool, chunk_size: u32, backoff: BackoffPolicy) -> Self {
+        Self {
+            pool,
+            chunk_size,
+            backoff,
+            adaptive_chunk_size: None,
+            stop_mode: StopMode::default(),
+        }
+    }
+
+    /// Sets the reaction to the stop signal. By default, the current chunk is migrated before stopping.
+    pub fn with_stop_mode(mut self, stop_mode: StopMode) -> Self {
+        self.stop_mode = stop_mode;
+        self
+    }
+
+    /// Enables adapting the chunk size to the chunk processing time. The chunk size specified
+    /// in the constructor is used as the initial one.
+    pub fn with_adaptive_chunk_size(mut self, params: AdaptiveChunkSize) -> Self {
+        self.adaptive_chunk_size = Some(params);
+        self
+    }
+
+    /// Runs the `migration` for the specified miniblock `range`.
+    pub async fn run(
+        &self,
+        migration: &mut impl MigrateChunk,
+        range: RangeInclusive<MiniblockNumber>,
+        stop_receiver: &watch::Receiver<bool>,
+    ) -> anyhow::Result<ChunkedMigrationOutput> {
+        anyhow::ensure!(self.chunk_size > 0, "Chunk size must be positive");
+        self.backoff.validate()?;
+        let mut chunk_size = self.chunk_size;
+        if let Some(params) = &self.adaptive_chunk_size {
+            params.validate()?;
+            chunk_size = chunk_size.clamp(params.min_chunk_size, params.max_chunk_size);
+        }
+        let (mut chunk_start, last_miniblock) = range.into_inner();
+        // The start of the next chunk is computed after each chunk, so it must not overflow.
+        anyhow::ensure!(
+            last_miniblock.checked_add(1).is_some(),
+            "Last miniblock #{last_miniblock} is out of range"
+        );
+
+        let mut rows_affected = 0;
+        let mut consecutive_migrated_chunks = 0_u32;
+        while chunk_start <= last_miniblock {
+            let chunk_started_at = Instant::now();
+            let mut storage = self.pool.access_storage_tagged("state_keeper").await?;
+            let chunk_end = chunk_start
+                .checked_add(chunk_size - 1)
+                .map_or(last_miniblock, |chunk_end| chunk_end.min(last_miniblock));
+            let chunk_end = migration
+                .adjust_chunk_end(&mut storage, chunk_end, last_miniblock)
+                .await?;
+            let chunk = chunk_start..=chunk_end;
+
+            let was_migrated = migration
+                .is_chunk_migrated(&mut storage, chunk.clone())
+                .await?;
+            if !was_migrated && self.stop_mode == StopMode::BeforeWrite && *stop_receiver.borrow() {
+                tracing::info!("Stop signal received; migration shutting down before migrating chunk {chunk:?}");
+                return Ok(ChunkedMigrationOutput {
+                    rows_affected,
+                    stopped_at: Some(chunk_start),
+                });
+            }
+            let chunk_rows_affected = if was_migrated {
+                0
+            } else {
+                migration.migrate_chunk(&mut storage, chunk.clone()).await?
+            };
+            drop(storage);
+            rows_affected += chunk_rows_affected;
+
+            let outcome = ChunkOutcome {
+                range: chunk,
+                was_migrated,
+                rows_affected: chunk_rows_affected,
+                elapsed: chunk_started_at.elapsed(),
+            };
+            if let (Some(params), false) = (&self.adaptive_chunk_size, was_migrated) {
+                let next_chunk_size = params.next_chunk_size(chunk_size, outcome.elapsed);
+                if next_chunk_size != chunk_size {
+                    tracing::debug!(
+                        "Changing chunk size from {chunk_size} to {next_chunk_size} miniblocks; \
+                         previous chunk took {:?}",
+                        outcome.elapsed
+                    );
+                    chunk_size = next_chunk_size;
+                }
+            }
+
+            let should_stop = migration.after_chunk(&outcome)?;
+            if should_stop || *stop_receiver.borrow() {
+                if !should_stop {
+                    tracing::info!("Stop signal received; migration shutting down");
+                }
+                return Ok(ChunkedMigrationOutput {
+                    rows_affected,
+                    stopped_at: Some(chunk_end + 1),
+                });
+            }
+            chunk_start = chunk_end + 1;
+
+            if was_migrated {
+                consecutive_migrated_chunks = 0;
+            } else {
+                consecutive_migrated_chunks = consecutive_migrated_chunks.saturating_add(1);
+                let delay = self.backoff.delay(consecutive_migrated_chunks);
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+
+        Ok(ChunkedMigrationOutput {
+            rows_affected,
+            stopped_at: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    /// Migration marking miniblocks as migrated in memory.
+    #[derive(Debug, Default)]
+    struct MockMigration {
+        migrated: HashSet<MiniblockNumber>,
+        processed_chunks: Vec<RangeInclusive<MiniblockNumber>>,
+    }
+
+    #[async_trait]
+    impl MigrateChunk for MockMigration {
+        async fn is_chunk_migrated(
+            &mut self,
+            _storage: &mut StorageProcessor<'_>,
+            chunk: RangeInclusive<MiniblockNumber>,
+        ) -> anyhow::Result<bool> {
+            self.processed_chunks.push(chunk.clone());
+            let (start, end) = chunk.into_inner();
+            Ok(start
+                .iter_to(end)
+                .all(|number| self.migrated.contains(&number)))
+        }
+
+        async fn migrate_chunk(
+            &mut self,
+            _storage: &mut StorageProcessor<'_>,
+            chunk: RangeInclusive<MiniblockNumber>,
+        ) -> anyhow::Result<u64> {
+            let (start, end) = chunk.into_inner();
+            let rows_affected = start
+                .iter_to(end)
+                .filter(|&number| self.migrated.insert(number))
+                .count();
+            Ok(rows_affected as u64)
+        }
+    }
+
+    #[tokio::test]
+    async fn running_chunked_migration() {
+        let pool = ConnectionPool::test_pool().await;
+        let runner = ChunkedMigration::new(pool, 2, BackoffPolicy::None);
+        let mut migration = MockMigration::default();
+        migration.migrated.insert(MiniblockNumber(3));
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+
+        let output = runner
+            .run(
+                &mut migration,
+                MiniblockNumber(0)..=MiniblockNumber(4),
+                &stop_receiver,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            output,
+            ChunkedMigrationOutput {
+                rows_affected: 4,
+                stopped_at: None,
+            }
+        );
+        assert_eq!(
+            migration.processed_chunks,
+            [
+                MiniblockNumber(0)..=MiniblockNumber(1),
+                MiniblockNumber(2)..=MiniblockNumber(3),
+                MiniblockNumber(4)..=MiniblockNumber(4),
+            ]
+        );
+
+        // All chunks are already migrated.
+        migration.processed_chunks.clear();
+        let output = runner
+            .run(
+                &mut migration,
+                MiniblockNumber(0)..=MiniblockNumber(4),
+                &stop_receiver,
+            )
+            .await
+            .unwrap();
+        assert_eq!(output.rows_affected, 0);
+        assert_eq!(output.stopped_at, None);
+        assert_eq!(migration.processed_chunks.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn stopping_and_resuming_chunked_migration() {
+        let pool = ConnectionPool::test_pool().await;
+        let runner =
+            ChunkedMigration::new(pool, 2, BackoffPolicy::Fixed(Duration::from_secs(1_000)));
+        let mut migration = MockMigration::default();
+        let (stop_sender, stop_receiver) = watch::channel(true); // signal stop right away
+
+        let output = runner
+            .run(
+                &mut migration,
+                MiniblockNumber(0)..=MiniblockNumber(4),
+                &stop_receiver,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            output,
+            ChunkedMigrationOutput {
+                rows_affected: 2,
+                stopped_at: Some(MiniblockNumber(2)),
+            }
+        );
+
+        stop_sender.send_replace(false);
+        let runner = ChunkedMigration {
+            backoff: BackoffPolicy::None,
+            ..runner
+        };
+        let output = runner
+            .run(
+                &mut migration,
+                MiniblockNumber(2)..=MiniblockNumber(4),
+                &stop_receiver,
+            )
+            .await
+            .unwrap();
+        assert_eq!(output.rows_affected, 3);
+        assert_eq!(output.stopped_at, None);
+        assert_eq!(migration.migrated.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn stopping_chunked_migration_before_write() {
+        let pool = ConnectionPool::test_pool().await;
+        let runner = ChunkedMigration::new(pool, 2, BackoffPolicy::None)
+            .with_stop_mode(StopMode::BeforeWrite);
+        let mut migration = MockMigration::default();
+        migration
+            .migrated
+            .extend([MiniblockNumber(0), MiniblockNumber(1)]);
+        let (_stop_sender, stop_receiver) = watch::channel(true); // signal stop right away
+
+        let output = runner
+            .run(
+                &mut migration,
+                MiniblockNumber(0)..=MiniblockNumber(4),
+                &stop_receiver,
+            )
+            .await
+            .unwrap();
+        // The first chunk is already migrated, so it's processed; the second one isn't migrated.
+        assert_eq!(
+            output,
+            ChunkedMigrationOutput {
+                rows_affected: 0,
+                stopped_at: Some(MiniblockNumber(2)),
+            }
+        );
+        assert_eq!(migration.migrated.len(), 2);
+    }
+
+    #[test]
+    fn adapting_chunk_size() {
+        let params = AdaptiveChunkSize {
+            target_duration: Duration::from_secs(1),
+            min_chunk_size: 10,
+            max_chunk_size: 100,
+        };
+        assert_eq!(params.next_chunk_size(20, Duration::from_millis(100)), 30);
+        assert_eq!(params.next_chunk_size(80, Duration::from_millis(100)), 100);
+        assert_eq!(params.next_chunk_size(100, Duration::from_millis(100)), 100);
+        assert_eq!(params.next_chunk_size(20, Duration::from_millis(700)), 20);
+        assert_eq!(params.next_chunk_size(20, Duration::from_secs(1)), 20);
+        assert_eq!(params.next_chunk_size(50, Duration::from_secs(2)), 25);
+        assert_eq!(params.next_chunk_size(15, Duration::from_secs(2)), 10);
+
+        let params = AdaptiveChunkSize {
+            min_chunk_size: 1,
+            ..params
+        };
+        assert_eq!(params.next_chunk_size(1, Duration::ZERO), 2);
+        assert_eq!(params.next_chunk_size(1, Duration::from_secs(2)), 1);
+    }
+
+    #[test]
+    fn adapting_chunk_size_over_multiple_chunks() {
+        let params = AdaptiveChunkSize {
+            target_duration: Duration::from_millis(200),
+            min_chunk_size: 2,
+            max_chunk_size: 16,
+        };
+        let chunk_durations =
+            [10, 10, 10, 10, 10, 300, 150, 10, 500, 500, 500].map(Duration::from_millis);
+        let mut chunk_size = 4;
+        let mut chunk_sizes = vec![chunk_size];
+        for elapsed in chunk_durations {
+            chunk_size = params.next_chunk_size(chunk_size, elapsed);
+            chunk_sizes.push(chunk_size);
+        }
+        // The chunk size grows until it reaches the max, is halved after the slow chunk, is retained
+        // after a chunk with duration close to the target, and is never reduced below the min.
+        assert_eq!(chunk_sizes, [4, 6, 9, 13, 16, 16, 8, 8, 12, 6, 3, 2]);
+    }
+
+    #[tokio::test]
+    async fn running_chunked_migration_with_adaptive_chunk_size() {
+        let pool = ConnectionPool::test_pool().await;
+        let runner = ChunkedMigration::new(pool, 4, BackoffPolicy::None).with_adaptive_chunk_size(
+            AdaptiveChunkSize {
+                target_duration: Duration::from_millis(200),
+                min_chunk_size: 2,
+                max_chunk_size: 16,
+            },
+        );
+        let mut migration = MockMigration::default();
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+
+        let output = runner
+            .run(
+                &mut migration,
+                MiniblockNumber(0)..=MiniblockNumber(67),
+                &stop_receiver,
+            )
+            .await
+            .unwrap();
+        assert_eq!(output.rows_affected, 68);
+        assert_eq!(output.stopped_at, None);
+        assert_eq!(migration.migrated.len(), 68);
+        // Chunk durations depend on the environment, so we only check that chunks are contiguous
+        // and their sizes stay within bounds. The last chunk may be truncated by the range end.
+        let (last_chunk, chunks) = migration.processed_chunks.split_last().unwrap();
+        assert_eq!(*last_chunk.end(), MiniblockNumber(67));
+        let mut expected_start = MiniblockNumber(0);
+        for chunk in chunks {
+            assert_eq!(*chunk.start(), expected_start);
+            let chunk_size = chunk.end().0 - chunk.start().0 + 1;
+            assert!((2..=16).contains(&chunk_size), "{chunk:?}");
+            expected_start = *chunk.end() + 1;
+        }
+        assert_eq!(*last_chunk.start(), expected_start);
+        assert!(last_chunk.end().0 - last_chunk.start().0 + 1 <= 16);
+    }
+
+    #[test]
+    fn computing_backoff_delays() {
+        assert_eq!(BackoffPolicy::None.delay(1), Duration::ZERO);
+        let fixed = BackoffPolicy::Fixed(Duration::from_millis(100));
+        assert_eq!(fixed.delay(1), Duration::from_millis(100));
+        assert_eq!(fixed.delay(10), Duration::from_millis(100));
+
+        let exponential = BackoffPolicy::Exponential {
+            base: Duration::from_millis(100),
+            max: Duration::from_secs(1),
+        };
+        let delays: Vec<_> = (1..=6).map(|count| exponential.delay(count)).collect();
+        assert_eq!(
+            delays,
+            [100, 200, 400, 800, 1_000, 1_000].map(Duration::from_millis)
+        );
+        assert_eq!(exponential.delay(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn chunked_migration_with_exponential_backoff() {
+        let pool = ConnectionPool::test_pool().await;
+        let backoff = BackoffPolicy::Exponential {
+            base: Duration::from_millis(20),
+            max: Duration::from_millis(50),
+        };
+        let runner = ChunkedMigration::new(pool, 1, backoff);
+        let mut migration = MockMigration::default();
+        // Already migrated miniblock resets the backoff delay.
+        migration.migrated.insert(MiniblockNumber(3));
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+
+        let started_at = Instant::now();
+        let output = runner
+            .run(
+                &mut migration,
+                MiniblockNumber(0)..=MiniblockNumber(4),
+                &stop_receiver,
+            )
+            .await
+            .unwrap();
+        let elapsed = started_at.elapsed();
+        assert_eq!(output.rows_affected, 4);
+        // Delays: 20ms, 40ms, 50ms (capped), none (migrated chunk), 20ms (reset).
+        assert!(elapsed >= Duration::from_millis(130), "{elapsed:?}");
+    }
+
+    #[tokio::test]
+    async fn chunked_migration_with_invalid_params() {
+        let pool = ConnectionPool::test_pool().await;
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let runner = ChunkedMigration::new(pool.clone(), 0, BackoffPolicy::None);
+        let err = runner
+            .run(
+                &mut MockMigration::default(),
+                MiniblockNumber(0)..=MiniblockNumber(4),
+                &stop_receiver,
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Chunk size must be positive"), "{err}");
+
+        let runner = ChunkedMigration::new(pool.clone(), 10, BackoffPolicy::None);
+        let err = runner
+            .run(
+                &mut MockMigration::default(),
+                MiniblockNumber(0)..=MiniblockNumber(u32::MAX),
+                &stop_receiver,
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("out of range"), "{err}");
+
+        let backoff = BackoffPolicy::Exponential {
+            base: Duration::from_secs(2),
+            max: Duration::from_secs(1),
+        };
+        let runner = ChunkedMigration::new(pool.clone(), 10, backoff);
+        let err = runner
+            .run(
+                &mut MockMigration::default(),
+                MiniblockNumber(0)..=MiniblockNumber(4),
+                &stop_receiver,
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("exceeds max delay"), "{err}");
+
+        let runner = ChunkedMigration::new(pool, 10, BackoffPolicy::None).with_adaptive_chunk_size(
+            AdaptiveChunkSize {
+                target_duration: Duration::from_secs(1),
+                min_chunk_size: 20,
+                max_chunk_size: 10,
+            },
+        );
+        let err = runner
+            .run(
+                &mut MockMigration::default(),
+                MiniblockNumber(0)..=MiniblockNumber(4),
+                &stop_receiver,
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("exceeds max chunk size"), "{err}");
+    }
+}
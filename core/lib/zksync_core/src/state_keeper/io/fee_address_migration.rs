@@ -2,12 +2,26 @@
 
 // FIXME (PLA-728): remove after 2nd phase of `fee_account_address` migration
 
-use std::time::{Duration, Instant};
+use std::{
+    collections::VecDeque,
+    fs,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
 use anyhow::Context as _;
+use async_trait::async_trait;
+use futures::{channel::oneshot, future};
+use serde::Serialize;
 use tokio::sync::watch;
 use zksync_dal::{ConnectionPool, StorageProcessor};
-use zksync_types::MiniblockNumber;
+use zksync_types::{L1BatchNumber, MiniblockNumber};
+
+use super::chunked_migration::{
+    AdaptiveChunkSize, BackoffPolicy, ChunkOutcome, ChunkedMigration, MigrateChunk, StopMode,
+};
+use crate::state_keeper::metrics::FEE_ADDRESS_MIGRATION_METRICS;
 
 /// Runs the migration for pending miniblocks.
 pub(crate) async fn migrate_pending_miniblocks(storage: &mut StorageProcessor<'_>) {
@@ -36,42 +50,302 @@ pub(crate) async fn migrate_pending_miniblocks(storage: &mut StorageProcessor<'_
 }
 
 /// Runs the migration for non-pending miniblocks. Should be run as a background task.
+///
+/// If `resume_from_batch` is set, miniblocks before the first miniblock of the specified L1 batch are not processed.
 pub(crate) async fn migrate_miniblocks(
     pool: ConnectionPool,
     last_miniblock: MiniblockNumber,
+    resume_from_batch: Option<L1BatchNumber>,
     stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<()> {
-    let MigrationOutput {
-        miniblocks_affected,
-    } = migrate_miniblocks_inner(
-        pool,
-        last_miniblock,
-        100_000,
-        Duration::from_secs(1),
-        stop_receiver,
-    )
-    .await?;
+    let mut config = MigrationConfig::builder(last_miniblock);
+    if let Some(l1_batch) = resume_from_batch {
+        config = config.resume_from_batch(l1_batch);
+    }
+    let config = config.build();
+    let output = migrate_miniblocks_inner(pool, config, stop_receiver).await?;
 
-    tracing::info!("Finished fee address migration with {miniblocks_affected} affected miniblocks");
+    let miniblocks_remaining = output.miniblocks_remaining;
+    tracing::info!("Finished {}", output.summary_line());
+    tracing::debug!("Fee address migration report: {}", output.to_json_report());
+    if miniblocks_remaining > 0 {
+        if let Some(eta) = output.estimated_remaining() {
+            tracing::info!(
+                "{miniblocks_remaining} miniblocks are not processed; estimated time to process them: {eta:?}"
+            );
+        }
+    }
     Ok(())
 }
 
-#[derive(Debug, Default)]
-struct MigrationOutput {
-    miniblocks_affected: u64,
+/// Runs the migration for all non-pending miniblocks, i.e., up to and including the last sealed miniblock.
+/// Should be run as a background task.
+///
+/// The last sealed miniblock is sampled once when the migration starts; miniblocks sealed during the migration
+/// are not processed (they should get the fee address when sealed, or will be processed by the next run).
+/// This ensures that the migration doesn't chase a moving target indefinitely.
+pub(crate) async fn migrate_miniblocks_to_tip(
+    pool: ConnectionPool,
+    stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    let mut storage = pool.access_storage_tagged("state_keeper").await?;
+    let last_miniblock = last_sealed_miniblock(&mut storage).await?;
+    drop(storage);
+
+    tracing::info!(
+        "Running fee address migration up to the last sealed miniblock #{last_miniblock}"
+    );
+    migrate_miniblocks(pool, last_miniblock, None, stop_receiver).await
 }
 
-/// It's important for the `chunk_size` to be a constant; this ensures that each chunk is migrated atomically.
-async fn migrate_miniblocks_inner(
+/// Returns the last sealed miniblock, or [`StorageNotInitialized`] error if there are no sealed miniblocks.
+async fn last_sealed_miniblock(
+    storage: &mut StorageProcessor<'_>,
+) -> anyhow::Result<MiniblockNumber> {
+    let sealed_miniblock = storage
+        .blocks_dal()
+        .get_sealed_miniblock_number()
+        .await
+        .context("Failed getting sealed miniblock number")?;
+    Ok(sealed_miniblock.ok_or(StorageNotInitialized)?)
+}
+
+/// Runs the migration for non-pending miniblocks until it's complete or the process receives an interrupt signal
+/// (SIGINT or SIGTERM), in which case the migration stops gracefully after the current chunk. This allows running
+/// the migration as a standalone task. Since a process can have only one interrupt handler, this function
+/// must not be used together with [`setup_sigint_handler()`](crate::setup_sigint_handler).
+pub async fn migrate_miniblocks_until_signal(
+    pool: ConnectionPool,
+    last_miniblock: MiniblockNumber,
+) -> anyhow::Result<()> {
+    let stop_receiver = stop_on_signal(crate::setup_sigint_handler());
+    migrate_miniblocks(pool, last_miniblock, None, stop_receiver).await
+}
+
+/// Returns a stop receiver that is flipped once `signal` resolves.
+fn stop_on_signal(signal: oneshot::Receiver<()>) -> watch::Receiver<bool> {
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    tokio::spawn(async move {
+        tokio::select! {
+            Ok(()) = signal => {
+                tracing::info!("Received interrupt signal; fee address migration will stop after the current chunk");
+                stop_sender.send_replace(true);
+            }
+            () = stop_sender.closed() => {
+                // The migration has finished; no need to wait for the signal.
+            }
+        }
+    });
+    stop_receiver
+}
+
+/// Runs the migration for non-pending miniblocks up to and including `last_miniblock` in a single pass
+/// using the provided `storage`. Unlike `migrate_miniblocks()`, the migration isn't split into chunks
+/// and cannot be stopped; this allows running it within a transaction started by the caller, e.g. as a part
+/// of a larger maintenance operation. Returns the number of affected miniblocks.
+pub async fn migrate_miniblocks_in_txn(
+    storage: &mut StorageProcessor<'_>,
+    last_miniblock: MiniblockNumber,
+) -> anyhow::Result<u64> {
+    #[allow(deprecated)]
+    let l1_batches_have_fee_account_address = storage
+        .blocks_dal()
+        .check_l1_batches_have_fee_account_address()
+        .await
+        .context("Failed getting metadata for l1_batches table")?;
+    if !l1_batches_have_fee_account_address {
+        tracing::info!("`l1_batches.fee_account_address` column is removed; assuming that the migration is complete");
+        return Ok(0);
+    }
+
+    let miniblocks = MiniblockNumber(0)..=last_miniblock;
+    #[allow(deprecated)]
+    let rows_affected = storage
+        .blocks_dal()
+        .copy_fee_account_address_for_miniblocks(miniblocks.clone())
+        .await
+        .with_context(|| format!("Failed migrating miniblocks {miniblocks:?}"))?;
+    Ok(rows_affected)
+}
+
+/// Estimated amount of work for the fee address migration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MigrationEstimate {
+    /// Total number of sealed miniblocks.
+    pub total_miniblocks: u64,
+    /// Number of miniblocks included into L1 batches that don't have fee address set. This is an upper bound,
+    /// since a fee address may legitimately be zero.
+    pub unmigrated_miniblocks: u64,
+}
+
+/// Estimates the amount of work for the fee address migration, so that operators can plan a maintenance window.
+/// This method is read-only.
+pub async fn estimate_migration_cost(pool: &ConnectionPool) -> anyhow::Result<MigrationEstimate> {
+    let mut storage = pool.access_storage_tagged("state_keeper").await?;
+    let sealed_miniblock = storage
+        .blocks_dal()
+        .get_sealed_miniblock_number()
+        .await
+        .context("Failed getting sealed miniblock number")?;
+    let total_miniblocks = sealed_miniblock.map_or(0, |number| {
+        MiniblockNumber::range_len(&(MiniblockNumber(0)..=number))
+    });
+
+    #[allow(deprecated)]
+    let l1_batches_have_fee_account_address = storage
+        .blocks_dal()
+        .check_l1_batches_have_fee_account_address()
+        .await
+        .context("Failed getting metadata for l1_batches table")?;
+    let unmigrated_miniblocks = if l1_batches_have_fee_account_address {
+        #[allow(deprecated)]
+        storage
+            .blocks_dal()
+            .count_miniblocks_without_fee_address()
+            .await
+            .context("Failed counting miniblocks without fee address")?
+    } else {
+        0
+    };
+
+    Ok(MigrationEstimate {
+        total_miniblocks,
+        unmigrated_miniblocks,
+    })
+}
+
+/// Estimates the number of row updates the migration will perform, which can be used for WAL / disk planning.
+/// Each miniblock included into an L1 batch without a fee address is updated exactly once, so unless new
+/// miniblocks are sealed concurrently, this matches the number of miniblocks affected by the subsequent migration.
+/// This method is read-only.
+pub async fn estimate_write_amplification(pool: &ConnectionPool) -> anyhow::Result<u64> {
+    let mut storage = pool.access_storage_tagged("state_keeper").await?;
+    #[allow(deprecated)]
+    let l1_batches_have_fee_account_address = storage
+        .blocks_dal()
+        .check_l1_batches_have_fee_account_address()
+        .await
+        .context("Failed getting metadata for l1_batches table")?;
+    if !l1_batches_have_fee_account_address {
+        return Ok(0);
+    }
+
+    #[allow(deprecated)]
+    storage
+        .blocks_dal()
+        .count_miniblocks_without_fee_address()
+        .await
+        .context("Failed counting miniblocks without fee address")
+}
+
+/// Report produced by [`dry_run_migration()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DryRunReport {
+    /// Chunks containing unmigrated miniblocks, in the increasing order.
+    pub unmigrated_chunks: Vec<RangeInclusive<MiniblockNumber>>,
+    /// Number of unmigrated miniblocks in these chunks, i.e., the number of miniblocks the migration would affect.
+    pub unmigrated_miniblocks: u64,
+    /// Whether the dry run was stopped by the stop signal before inspecting the entire range.
+    pub stopped: bool,
+}
+
+/// Walks the same chunks of miniblocks as [`migrate_miniblocks()`] without migrating them, and reports
+/// the chunks that would be migrated. Like the migration, the dry run can be cancelled via `stop_receiver`,
+/// in which case only the inspected chunks are reported. This method is read-only.
+pub async fn dry_run_migration(
     pool: ConnectionPool,
     last_miniblock: MiniblockNumber,
     chunk_size: u32,
-    sleep_interval: Duration,
     stop_receiver: watch::Receiver<bool>,
-) -> anyhow::Result<MigrationOutput> {
-    anyhow::ensure!(chunk_size > 0, "Chunk size must be positive");
+) -> anyhow::Result<DryRunReport> {
+    let mut storage = pool.access_storage_tagged("state_keeper").await?;
+    let sealed_miniblock = last_sealed_miniblock(&mut storage).await?;
+    #[allow(deprecated)]
+    let l1_batches_have_fee_account_address = storage
+        .blocks_dal()
+        .check_l1_batches_have_fee_account_address()
+        .await
+        .context("Failed getting metadata for l1_batches table")?;
+    drop(storage);
+    if !l1_batches_have_fee_account_address {
+        return Ok(DryRunReport::default());
+    }
+
+    let last_miniblock = last_miniblock.min(sealed_miniblock);
+    let mut dry_run = DryRunMigration::default();
+    let output = ChunkedMigration::new(pool, chunk_size, BackoffPolicy::None)
+        .run(
+            &mut dry_run,
+            MiniblockNumber(0)..=last_miniblock,
+            &stop_receiver,
+        )
+        .await?;
+    Ok(DryRunReport {
+        stopped: output.stopped_at.is_some(),
+        ..dry_run.report
+    })
+}
+
+/// [`MigrateChunk`] implementation for [`dry_run_migration()`] that records unmigrated chunks instead of
+/// migrating them.
+#[derive(Debug, Default)]
+struct DryRunMigration {
+    /// Number of unmigrated miniblocks in the currently processed chunk.
+    unmigrated_in_chunk: u64,
+    report: DryRunReport,
+}
+
+#[async_trait]
+impl MigrateChunk for DryRunMigration {
+    async fn is_chunk_migrated(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        chunk: RangeInclusive<MiniblockNumber>,
+    ) -> anyhow::Result<bool> {
+        let unmigrated_miniblocks = list_unmigrated_miniblocks(storage, chunk).await?;
+        self.unmigrated_in_chunk = unmigrated_miniblocks.len() as u64;
+        Ok(unmigrated_miniblocks.is_empty())
+    }
+
+    async fn migrate_chunk(
+        &mut self,
+        _storage: &mut StorageProcessor<'_>,
+        chunk: RangeInclusive<MiniblockNumber>,
+    ) -> anyhow::Result<u64> {
+        tracing::debug!(
+            "Dry run: chunk {chunk:?} has {} unmigrated miniblocks",
+            self.unmigrated_in_chunk
+        );
+        self.report.unmigrated_chunks.push(chunk);
+        self.report.unmigrated_miniblocks += self.unmigrated_in_chunk;
+        Ok(0)
+    }
+}
 
+/// Report produced by [`verify_migration()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerificationReport {
+    /// Chunks considered migrated (i.e., with the migrated first miniblock) that contain unmigrated miniblocks,
+    /// together with the number of unmigrated miniblocks; in the increasing order.
+    pub inconsistent_chunks: Vec<(RangeInclusive<MiniblockNumber>, u64)>,
+    /// Number of inspected chunks considered migrated.
+    pub migrated_chunks: u64,
+    /// Whether the verification was stopped by the stop signal before inspecting the entire range.
+    pub stopped: bool,
+}
+
+/// Walks the same chunks of miniblocks as [`migrate_miniblocks()`] and checks that each chunk considered migrated
+/// is fully migrated. Partially migrated chunks can occur e.g. if a migration was interrupted in the middle
+/// of a chunk before chunks were migrated atomically. Chunks that are not migrated at all are not reported;
+/// use [`dry_run_migration()`] to find them. This method is read-only.
+pub async fn verify_migration(
+    pool: ConnectionPool,
+    last_miniblock: MiniblockNumber,
+    chunk_size: u32,
+    stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<VerificationReport> {
     let mut storage = pool.access_storage_tagged("state_keeper").await?;
+    let sealed_miniblock = last_sealed_miniblock(&mut storage).await?;
     #[allow(deprecated)]
     let l1_batches_have_fee_account_address = storage
         .blocks_dal()
@@ -80,98 +354,1896 @@ async fn migrate_miniblocks_inner(
         .context("Failed getting metadata for l1_batches table")?;
     drop(storage);
     if !l1_batches_have_fee_account_address {
-        tracing::info!("`l1_batches.fee_account_address` column is removed; assuming that the migration is complete");
-        return Ok(MigrationOutput::default());
+        return Ok(VerificationReport::default());
     }
 
-    let mut chunk_start = MiniblockNumber(0);
-    let mut miniblocks_affected = 0;
+    let last_miniblock = last_miniblock.min(sealed_miniblock);
+    let mut verification = VerifyMigration::default();
+    let output = ChunkedMigration::new(pool, chunk_size, BackoffPolicy::None)
+        .run(
+            &mut verification,
+            MiniblockNumber(0)..=last_miniblock,
+            &stop_receiver,
+        )
+        .await?;
+    let report = VerificationReport {
+        stopped: output.stopped_at.is_some(),
+        ..verification.report
+    };
+    if !report.inconsistent_chunks.is_empty() {
+        tracing::warn!(
+            "Fee address migration verification found {} partially migrated chunks: {:?}",
+            report.inconsistent_chunks.len(),
+            report.inconsistent_chunks
+        );
+    }
+    Ok(report)
+}
 
-    tracing::info!(
-        "Migrating `fee_account_address` for miniblocks {chunk_start}..={last_miniblock} \
-         in chunks of {chunk_size} miniblocks"
-    );
-    while chunk_start <= last_miniblock {
-        let chunk_end = last_miniblock.min(chunk_start + chunk_size - 1);
-        let chunk = chunk_start..=chunk_end;
+/// [`MigrateChunk`] implementation for [`verify_migration()`]. All checks are performed in `is_chunk_migrated()`,
+/// which always returns `true`, so that chunks are never migrated.
+#[derive(Debug, Default)]
+struct VerifyMigration {
+    report: VerificationReport,
+}
 
-        let mut storage = pool.access_storage_tagged("state_keeper").await?;
-        let is_chunk_migrated = is_fee_address_migrated(&mut storage, chunk_start).await?;
+#[async_trait]
+impl MigrateChunk for VerifyMigration {
+    async fn is_chunk_migrated(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        chunk: RangeInclusive<MiniblockNumber>,
+    ) -> anyhow::Result<bool> {
+        #[allow(deprecated)]
+        let is_migrated = storage
+            .blocks_dal()
+            .are_chunks_migrated(&[chunk.clone()])
+            .await
+            .with_context(|| format!("Failed checking migration status of chunk {chunk:?}"))?;
+        if !is_migrated.first().copied().unwrap_or(false) {
+            return Ok(true);
+        }
 
-        if is_chunk_migrated {
-            tracing::debug!("`fee_account_address` is migrated for chunk {chunk:?}");
+        self.report.migrated_chunks += 1;
+        #[allow(deprecated)]
+        let unmigrated_count = storage
+            .blocks_dal()
+            .verify_chunk_consistency(chunk.clone())
+            .await
+            .with_context(|| format!("Failed verifying chunk {chunk:?}"))?;
+        if unmigrated_count > 0 {
+            tracing::debug!(
+                "Chunk {chunk:?} is considered migrated, but has {unmigrated_count} unmigrated miniblocks"
+            );
+            self.report
+                .inconsistent_chunks
+                .push((chunk, unmigrated_count));
+        }
+        Ok(true)
+    }
+
+    async fn migrate_chunk(
+        &mut self,
+        _storage: &mut StorageProcessor<'_>,
+        chunk: RangeInclusive<MiniblockNumber>,
+    ) -> anyhow::Result<u64> {
+        anyhow::bail!(
+            "Verification must not migrate chunks, but was requested to migrate {chunk:?}"
+        )
+    }
+}
+
+/// Estimates the amount of data (in bytes) written by the migration for a chunk of `chunk_size` miniblocks,
+/// based on the average size of the latest miniblock rows. Since Postgres writes a new row version
+/// for each updated row, this can be used to choose a chunk size. Returns 0 if there are no miniblocks.
+pub async fn estimate_chunk_memory(
+    pool: &ConnectionPool,
+    chunk_size: u32,
+) -> anyhow::Result<usize> {
+    const SAMPLE_SIZE: u32 = 1_000;
+
+    let mut storage = pool.access_storage_tagged("state_keeper").await?;
+    #[allow(deprecated)]
+    let avg_row_size = storage
+        .blocks_dal()
+        .average_miniblock_row_size(SAMPLE_SIZE)
+        .await
+        .context("Failed getting average miniblock row size")?;
+    let avg_row_size = avg_row_size.unwrap_or(0) as usize;
+    Ok(avg_row_size.saturating_mul(chunk_size as usize))
+}
+
+/// Columns that must be present in the DB schema for the migration to run, as `(table, column)` pairs.
+const REQUIRED_COLUMNS: &[(&str, &str)] = &[("miniblocks", "fee_account_address")];
+
+/// Checks that all `columns` are present in the DB schema, so that the migration fails with a clear error
+/// rather than an obscure SQL error if it's run against an outdated schema.
+async fn ensure_schema_is_up_to_date(
+    storage: &mut StorageProcessor<'_>,
+    columns: &[(&str, &str)],
+) -> anyhow::Result<()> {
+    for &(table, column) in columns {
+        let has_column = storage
+            .system_dal()
+            .has_column(table, column)
+            .await
+            .with_context(|| format!("Failed checking whether column `{table}.{column}` exists"))?;
+        anyhow::ensure!(
+            has_column,
+            "DB schema is not up to date: column `{table}.{column}` is missing; run migrations first"
+        );
+    }
+    Ok(())
+}
+
+/// Error returned by the migration if there are no sealed miniblocks in the storage (e.g., genesis is not performed yet).
+#[derive(Debug, thiserror::Error)]
+#[error("storage is not initialized: there are no sealed miniblocks")]
+struct StorageNotInitialized;
+
+#[derive(Debug, Default, Serialize)]
+struct MigrationOutput {
+    miniblocks_affected: u64,
+    /// Number of miniblocks in the migrated range that weren't processed (e.g., because of a stop signal).
+    miniblocks_remaining: u64,
+    /// First miniblock that should be processed by the next migration run. `None` if the migration is complete.
+    resume_from: Option<MiniblockNumber>,
+    /// Whether planner statistics for the `miniblocks` table were updated after the migration.
+    miniblocks_table_analyzed: bool,
+    /// Number of processed chunks, including chunks that were already migrated.
+    chunks_processed: u64,
+    elapsed: Duration,
+    /// Throughput (miniblocks per second) over the last migrated chunks; see [`ThroughputTracker`].
+    /// `None` if no chunks were migrated during the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current_rate: Option<f64>,
+    /// Per-chunk timings; only collected if requested.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    chunk_timings: Vec<ChunkTiming>,
+}
+
+/// Profiling record for a single chunk processed by the migration.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct ChunkTiming {
+    range: RangeInclusive<MiniblockNumber>,
+    elapsed: Duration,
+    miniblocks_affected: u64,
+}
+
+/// Tracks migration throughput over a sliding window of recently processed chunks. Compared to the average
+/// throughput over the entire run, this smooths out per-chunk variance while reacting to throughput changes.
+#[derive(Debug)]
+struct ThroughputTracker {
+    window_size: usize,
+    /// Number of affected miniblocks and processing time for recent chunks, oldest first.
+    window: VecDeque<(u64, Duration)>,
+}
+
+impl ThroughputTracker {
+    /// Number of chunks the throughput is computed over during the migration.
+    const DEFAULT_WINDOW_SIZE: usize = 10;
+
+    fn new(window_size: usize) -> Self {
+        assert!(window_size > 0, "window size must be positive");
+        Self {
+            window_size,
+            window: VecDeque::with_capacity(window_size),
+        }
+    }
+
+    fn observe(&mut self, miniblocks_affected: u64, elapsed: Duration) {
+        if self.window.len() == self.window_size {
+            self.window.pop_front();
+        }
+        self.window.push_back((miniblocks_affected, elapsed));
+    }
+
+    /// Returns the number of affected miniblocks per second over the window, or `None` if no time
+    /// was spent on chunks in the window.
+    fn current_rate(&self) -> Option<f64> {
+        let (miniblocks, elapsed) = self.window.iter().fold(
+            (0_u64, Duration::ZERO),
+            |(miniblocks, elapsed), &(chunk_miniblocks, chunk_elapsed)| {
+                (miniblocks + chunk_miniblocks, elapsed + chunk_elapsed)
+            },
+        );
+        (!elapsed.is_zero()).then(|| miniblocks as f64 / elapsed.as_secs_f64())
+    }
+}
+
+impl MigrationOutput {
+    /// Returns a structured report on the migration that can be consumed by automated tooling.
+    fn to_json_report(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("failed serializing migration output")
+    }
+
+    /// Returns a concise one-line summary of the migration suitable for alerting integrations, such as
+    /// `fee address migration: 1,234,567 miniblocks affected in 12m34s (completed)`.
+    fn summary_line(&self) -> String {
+        let status = if self.resume_from.is_none() && self.miniblocks_remaining == 0 {
+            "completed".to_owned()
         } else {
-            tracing::debug!("Migrating `fee_account_address` for miniblocks chunk {chunk:?}");
+            format!(
+                "stopped; {} miniblocks remaining",
+                format_with_thousands_separators(self.miniblocks_remaining)
+            )
+        };
+        format!(
+            "fee address migration: {} miniblocks affected in {} ({status})",
+            format_with_thousands_separators(self.miniblocks_affected),
+            format_duration(self.elapsed)
+        )
+    }
 
-            #[allow(deprecated)]
-            let rows_affected = storage
-                .blocks_dal()
-                .copy_fee_account_address_for_miniblocks(chunk.clone())
-                .await
-                .with_context(|| format!("Failed migrating miniblocks chunk {chunk:?}"))?;
-            tracing::debug!("Migrated {rows_affected} miniblocks in chunk {chunk:?}");
-            miniblocks_affected += rows_affected;
+    /// Estimates the time necessary to process the remaining miniblocks based on the throughput
+    /// observed during the run. Returns `None` if there is no throughput data yet.
+    fn estimated_remaining(&self) -> Option<Duration> {
+        if self.miniblocks_affected == 0 || self.elapsed.is_zero() {
+            return None;
         }
-        drop(storage);
+        let miniblocks_per_sec = self.miniblocks_affected as f64 / self.elapsed.as_secs_f64();
+        Some(Duration::from_secs_f64(
+            self.miniblocks_remaining as f64 / miniblocks_per_sec,
+        ))
+    }
+}
 
-        if *stop_receiver.borrow() {
-            tracing::info!("Stop signal received; fee address migration shutting down");
-            return Ok(MigrationOutput {
-                miniblocks_affected,
-            });
+/// Formats an integer with thousands separators, e.g. `1,234,567`.
+fn format_with_thousands_separators(value: u64) -> String {
+    let digits = value.to_string();
+    let mut output = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            output.push(',');
         }
-        chunk_start = chunk_end + 1;
+        output.push(digit);
+    }
+    output
+}
+
+/// Formats a duration with a second precision, e.g. `1h02m03s`, `12m34s` or `5s`.
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, minutes, secs) = (secs / 3_600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}h{minutes:02}m{secs:02}s")
+    } else if minutes > 0 {
+        format!("{minutes}m{secs:02}s")
+    } else {
+        format!("{secs}s")
+    }
+}
+
+/// Tunable parameters of the fee address migration. Should be created using [`Self::builder()`], which sets defaults
+/// used by [`migrate_miniblocks()`].
+#[derive(Debug, Clone)]
+pub(crate) struct MigrationConfig {
+    /// First miniblock to migrate. Usually, 0; a greater value allows re-running the migration
+    /// for a specific range of miniblocks.
+    from_miniblock: MiniblockNumber,
+    /// Last miniblock to migrate (inclusive). Capped to the last sealed miniblock at the start of the migration.
+    last_miniblock: MiniblockNumber,
+    /// Number of miniblocks in a chunk. It's important for the chunk size to be a constant; this ensures
+    /// that each chunk is migrated atomically.
+    chunk_size: u32,
+    /// If set, `chunk_size` is only used as the initial chunk size, and the size is adapted to the time it took
+    /// to migrate the previous chunk (see [`AdaptiveChunkSize`]). Since only the size of the following chunks
+    /// is changed, each chunk is still migrated atomically.
+    adaptive_chunk_size: Option<AdaptiveChunkSize>,
+    /// If greater than 1, up to `concurrency` consecutive chunks are migrated in parallel using separate connections
+    /// from the pool, so the pool should have at least `concurrency + 1` connections. All chunks in such a group
+    /// are finished before the stop signal is checked or the checkpoint is updated; chunk sizes bounded by
+    /// `adaptive_chunk_size` and the other chunk-level options apply to the entire group.
+    concurrency: usize,
+    /// If set, each chunk is migrated using multiple DB statements updating at most the specified number
+    /// of miniblocks each, so that statements don't exceed timeouts. The chunk is fully drained before
+    /// the migration proceeds to the next chunk.
+    max_rows_per_statement: Option<u32>,
+    /// Delay between migrated chunks.
+    backoff: BackoffPolicy,
+    /// If set, chunk ends are extended to the last miniblock of the containing L1 batch, so that an L1 batch
+    /// is never split between chunks.
+    align_to_l1_batches: bool,
+    /// If set, the migration stops gracefully once the number of affected miniblocks reaches it, which allows
+    /// running the migration incrementally. Since chunks are processed atomically, the number of affected
+    /// miniblocks may exceed the cap by less than a single chunk.
+    max_miniblocks: Option<u64>,
+    /// If set, the `miniblocks` table is analyzed once the migration is complete and has affected at least
+    /// one miniblock, so that planner statistics don't become stale after the bulk update. Off by default,
+    /// so that the node doesn't run `ANALYZE` on each start.
+    analyze_after: bool,
+    /// If set, a [`ChunkTiming`] record is collected for each processed chunk and returned in the output,
+    /// which can be used to profile the migration. This is off by default to not grow memory during
+    /// long migrations.
+    collect_timings: bool,
+    /// If set, each chunk is checked right after it's migrated, and the migration is aborted with an error
+    /// if the chunk still contains unmigrated miniblocks (e.g., because an L1 batch doesn't have a fee address).
+    /// This trades speed for safety.
+    verify_each_chunk: bool,
+    /// If set, the first miniblock of the next chunk is persisted to this file after each chunk, and the migration
+    /// resumes from the persisted miniblock on start. This allows resuming the migration across process restarts
+    /// without inspecting the migrated data. Once the migration is complete, the checkpoint is marked
+    /// as [completed](Checkpoint::Completed), and subsequent runs with the same checkpoint return immediately.
+    /// Thus, `last_miniblock` should be the last sealed miniblock if the checkpoint is used.
+    checkpoint_path: Option<PathBuf>,
+    /// If set, the migration starts from the first miniblock of the specified L1 batch (or from the checkpoint,
+    /// if it's further). The migration returns an error if the L1 batch is not sealed.
+    resume_from_batch: Option<L1BatchNumber>,
+    /// Determines whether the current chunk is finished once the stop signal is received, or the migration
+    /// stops before the next DB write (see [`StopMode`]). In both cases, only committed chunks count as processed.
+    stop_mode: StopMode,
+}
 
-        if !is_chunk_migrated {
-            tokio::time::sleep(sleep_interval).await;
+impl MigrationConfig {
+    /// Creates a builder for migrating miniblocks up to and including `last_miniblock`. By default, parameters
+    /// are suitable for running the migration as a background task on a live node.
+    pub fn builder(last_miniblock: MiniblockNumber) -> MigrationConfigBuilder {
+        MigrationConfigBuilder {
+            config: Self {
+                from_miniblock: MiniblockNumber(0),
+                last_miniblock,
+                chunk_size: 100_000,
+                adaptive_chunk_size: None,
+                concurrency: 1,
+                max_rows_per_statement: None,
+                backoff: BackoffPolicy::Fixed(Duration::from_secs(1)),
+                align_to_l1_batches: false,
+                max_miniblocks: None,
+                analyze_after: false,
+                collect_timings: false,
+                verify_each_chunk: false,
+                checkpoint_path: None,
+                resume_from_batch: None,
+                stop_mode: StopMode::AfterChunk,
+            },
         }
     }
+}
 
-    Ok(MigrationOutput {
-        miniblocks_affected,
-    })
+/// Builder for [`MigrationConfig`]. See the config fields for the description of parameters.
+#[derive(Debug, Clone)]
+pub(crate) struct MigrationConfigBuilder {
+    config: MigrationConfig,
 }
 
-#[allow(deprecated)]
-async fn is_fee_address_migrated(
-    storage: &mut StorageProcessor<'_>,
-    miniblock: MiniblockNumber,
-) -> anyhow::Result<bool> {
-    storage
+impl MigrationConfigBuilder {
+    /// Starts the migration from the first miniblock of the specified L1 batch.
+    pub fn resume_from_batch(mut self, l1_batch: L1BatchNumber) -> Self {
+        self.config.resume_from_batch = Some(l1_batch);
+        self
+    }
+
+    pub fn build(self) -> MigrationConfig {
+        self.config
+    }
+}
+
+/// Migrates miniblocks according to the provided `config` (see [`MigrationConfig`] for the description
+/// of parameters) until the migration is complete or `stop_receiver` is triggered.
+///
+/// `last_miniblock` from the config is capped to the last sealed miniblock at the start of the migration.
+/// Miniblocks sealed while the migration is running are not in scope, even if `last_miniblock` covers them.
+async fn migrate_miniblocks_inner(
+    pool: ConnectionPool,
+    config: MigrationConfig,
+    stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<MigrationOutput> {
+    let MigrationConfig {
+        from_miniblock,
+        last_miniblock,
+        chunk_size,
+        adaptive_chunk_size,
+        concurrency,
+        max_rows_per_statement,
+        backoff,
+        align_to_l1_batches,
+        max_miniblocks,
+        analyze_after,
+        collect_timings,
+        verify_each_chunk,
+        checkpoint_path,
+        resume_from_batch,
+        stop_mode,
+    } = config;
+    let checkpoint_path = checkpoint_path.as_deref();
+
+    anyhow::ensure!(chunk_size > 0, "Chunk size must be positive");
+    anyhow::ensure!(concurrency > 0, "Concurrency must be positive");
+    anyhow::ensure!(
+        max_rows_per_statement != Some(0),
+        "Max rows per statement must be positive"
+    );
+    let group_size = u32::try_from(concurrency)
+        .ok()
+        .and_then(|concurrency| chunk_size.checked_mul(concurrency))
+        .with_context(|| {
+            format!("Chunk size {chunk_size} multiplied by concurrency {concurrency} overflows")
+        })?;
+    // The start of the next chunk is computed after each chunk, so it must not overflow.
+    anyhow::ensure!(
+        last_miniblock.checked_add(1).is_some(),
+        "Last miniblock #{last_miniblock} is out of range"
+    );
+
+    let mut storage = pool.access_storage_tagged("state_keeper").await?;
+    ensure_schema_is_up_to_date(&mut storage, REQUIRED_COLUMNS).await?;
+    let sealed_miniblock = last_sealed_miniblock(&mut storage).await?;
+    // Miniblocks sealed after this point are not in scope of the migration; they will get the fee address
+    // on their own, or will be processed by the next migration run.
+    let last_miniblock = if last_miniblock > sealed_miniblock {
+        tracing::info!(
+            "Capping last miniblock for fee address migration #{last_miniblock} to the last sealed miniblock #{sealed_miniblock}"
+        );
+        sealed_miniblock
+    } else {
+        last_miniblock
+    };
+
+    #[allow(deprecated)]
+    let l1_batches_have_fee_account_address = storage
         .blocks_dal()
-        .is_fee_address_migrated(miniblock)
+        .check_l1_batches_have_fee_account_address()
         .await
-        .with_context(|| format!("Failed getting fee address for miniblock #{miniblock}"))?
-        .with_context(|| format!("Miniblock #{miniblock} disappeared"))
-}
+        .context("Failed getting metadata for l1_batches table")?;
+    if !l1_batches_have_fee_account_address {
+        tracing::info!("`l1_batches.fee_account_address` column is removed; assuming that the migration is complete");
+        return Ok(MigrationOutput::default());
+    }
 
-#[cfg(test)]
-mod tests {
-    use test_casing::test_casing;
-    use zksync_contracts::BaseSystemContractsHashes;
-    use zksync_types::{
-        block::L1BatchHeader, Address, L1BatchNumber, ProtocolVersion, ProtocolVersionId,
+    let mut chunk_start = from_miniblock;
+    if let Some(l1_batch_number) = resume_from_batch {
+        let (first_miniblock, _) = storage
+            .blocks_dal()
+            .get_miniblock_range_of_l1_batch(l1_batch_number)
+            .await
+            .with_context(|| {
+                format!("Failed getting miniblock range for L1 batch #{l1_batch_number}")
+            })?
+            .with_context(|| format!("L1 batch #{l1_batch_number} is not sealed"))?;
+        tracing::info!(
+            "Resuming fee address migration from L1 batch #{l1_batch_number} (miniblock #{first_miniblock})"
+        );
+        chunk_start = chunk_start.max(first_miniblock);
+    }
+    drop(storage);
+
+    let started_at = Instant::now();
+    if let Some(path) = checkpoint_path {
+        remove_stale_checkpoint_files(path)?;
+        match read_checkpoint(path)? {
+            Some(Checkpoint::InProgress(checkpoint)) => {
+                tracing::info!("Resuming fee address migration from checkpoint {checkpoint}");
+                chunk_start = chunk_start.max(checkpoint);
+            }
+            Some(Checkpoint::Completed) => {
+                tracing::info!(
+                    "Checkpoint {path:?} is marked as completed; skipping the migration"
+                );
+                return Ok(MigrationOutput::default());
+            }
+            None => { /* start from scratch */ }
+        }
+    }
+    let mut migration = FeeAddressMigration {
+        pool: pool.clone(),
+        concurrency,
+        max_rows_per_statement,
+        last_miniblock,
+        align_to_l1_batches,
+        max_miniblocks,
+        collect_timings,
+        verify_each_chunk,
+        checkpoint_path,
+        unmigrated_miniblocks: vec![],
+        miniblocks_affected: 0,
+        chunks_processed: 0,
+        chunk_timings: vec![],
+        throughput: ThroughputTracker::new(ThroughputTracker::DEFAULT_WINDOW_SIZE),
     };
 
-    use super::*;
-    use crate::utils::testonly::create_miniblock;
+    tracing::info!(
+        "Migrating `fee_account_address` for miniblocks {chunk_start}..={last_miniblock} \
+         in chunks of {chunk_size} miniblocks with concurrency {concurrency}"
+    );
+    let mut runner =
+        ChunkedMigration::new(pool.clone(), group_size, backoff).with_stop_mode(stop_mode);
+    if let Some(params) = adaptive_chunk_size {
+        runner = runner.with_adaptive_chunk_size(params);
+    }
+    let output = runner
+        .run(&mut migration, chunk_start..=last_miniblock, &stop_receiver)
+        .await?;
+    let FeeAddressMigration {
+        miniblocks_affected,
+        chunks_processed,
+        chunk_timings,
+        throughput,
+        ..
+    } = migration;
+    let current_rate = throughput.current_rate();
+
+    if let Some(next_miniblock) = output.stopped_at {
+        return Ok(MigrationOutput {
+            miniblocks_affected,
+            miniblocks_remaining: MiniblockNumber::range_len(&(next_miniblock..=last_miniblock)),
+            resume_from: (next_miniblock <= last_miniblock).then_some(next_miniblock),
+            miniblocks_table_analyzed: false,
+            chunks_processed,
+            elapsed: started_at.elapsed(),
+            current_rate,
+            chunk_timings,
+        });
+    }
+
+    if let Some(path) = checkpoint_path {
+        write_checkpoint(path, Checkpoint::Completed)?;
+    }
+
+    let miniblocks_table_analyzed = analyze_after && miniblocks_affected > 0;
+    if miniblocks_table_analyzed {
+        let mut storage = pool.access_storage_tagged("state_keeper").await?;
+        #[allow(deprecated)]
+        storage
+            .blocks_dal()
+            .analyze_miniblocks_table()
+            .await
+            .context("Failed analyzing miniblocks table")?;
+        tracing::info!("Updated planner statistics for miniblocks table");
+    }
+
+    Ok(MigrationOutput {
+        miniblocks_affected,
+        miniblocks_remaining: 0,
+        resume_from: None,
+        miniblocks_table_analyzed,
+        chunks_processed,
+        elapsed: started_at.elapsed(),
+        current_rate,
+        chunk_timings,
+    })
+}
+
+/// Fee address migration logic for [`ChunkedMigration`].
+#[derive(Debug)]
+struct FeeAddressMigration<'a> {
+    pool: ConnectionPool,
+    concurrency: usize,
+    max_rows_per_statement: Option<u32>,
+    last_miniblock: MiniblockNumber,
+    align_to_l1_batches: bool,
+    max_miniblocks: Option<u64>,
+    collect_timings: bool,
+    verify_each_chunk: bool,
+    checkpoint_path: Option<&'a Path>,
+    /// Unmigrated miniblocks in the currently processed chunk.
+    unmigrated_miniblocks: Vec<MiniblockNumber>,
+    miniblocks_affected: u64,
+    chunks_processed: u64,
+    chunk_timings: Vec<ChunkTiming>,
+    throughput: ThroughputTracker,
+}
+
+impl FeeAddressMigration<'_> {
+    /// Interval (in processed chunks) between progress logs.
+    const PROGRESS_LOG_INTERVAL: u64 = 10;
+
+    /// Splits unmigrated miniblocks in the current group of chunks into up to `concurrency` parts and migrates them
+    /// in parallel, each part using a separate connection. Returns the total number of affected miniblocks.
+    async fn migrate_concurrently(
+        &self,
+        group: &RangeInclusive<MiniblockNumber>,
+    ) -> anyhow::Result<u64> {
+        let part_len = self.unmigrated_miniblocks.len().div_ceil(self.concurrency);
+        let part_futures = self.unmigrated_miniblocks.chunks(part_len).map(|part| {
+            let span = part[0]..=part[part.len() - 1];
+            async move {
+                let mut storage = self.pool.access_storage_tagged("state_keeper").await?;
+                copy_fee_addresses(&mut storage, span.clone(), self.max_rows_per_statement)
+                    .await
+                    .with_context(|| format!("Failed migrating span {span:?} of chunk {group:?}"))
+            }
+        });
+        let rows_affected = future::try_join_all(part_futures).await?;
+        Ok(rows_affected.into_iter().sum())
+    }
+
+    /// Reports migration progress after processing a chunk ending at `chunk_end`. This is done for all chunks,
+    /// including already migrated ones, so that progress is visible for restarted migrations as well.
+    fn report_progress(&self, chunk: &RangeInclusive<MiniblockNumber>) {
+        let (chunk_start, chunk_end) = (*chunk.start(), *chunk.end());
+        let processed_miniblocks = MiniblockNumber::range_len(&(MiniblockNumber(0)..=chunk_end));
+        let total_miniblocks =
+            MiniblockNumber::range_len(&(MiniblockNumber(0)..=self.last_miniblock));
+        let progress_percent = processed_miniblocks as f64 / total_miniblocks as f64 * 100.0;
+
+        let metrics = &FEE_ADDRESS_MIGRATION_METRICS;
+        metrics.chunk_start.set(chunk_start.0.into());
+        metrics.miniblocks_affected.set(self.miniblocks_affected);
+        metrics.progress_percent.set(progress_percent);
+        metrics.chunks_processed.inc();
+
+        if self.chunks_processed % Self::PROGRESS_LOG_INTERVAL == 0 {
+            tracing::info!(
+                "Fee address migration progress: processed {} chunks up to miniblock #{chunk_end} ({progress_percent:.1}%); \
+                 {} miniblocks affected so far",
+                self.chunks_processed,
+                self.miniblocks_affected
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl MigrateChunk for FeeAddressMigration<'_> {
+    async fn adjust_chunk_end(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        chunk_end: MiniblockNumber,
+        last_miniblock: MiniblockNumber,
+    ) -> anyhow::Result<MiniblockNumber> {
+        if self.align_to_l1_batches {
+            align_to_l1_batch(storage, chunk_end, last_miniblock).await
+        } else {
+            Ok(chunk_end)
+        }
+    }
+
+    async fn is_chunk_migrated(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        chunk: RangeInclusive<MiniblockNumber>,
+    ) -> anyhow::Result<bool> {
+        // Check each miniblock in the chunk rather than only its start, so that partially migrated chunks
+        // (e.g., after manual DB edits) are handled, and only the span of unmigrated miniblocks is updated.
+        let unmigrated_miniblocks = list_unmigrated_miniblocks(storage, chunk.clone()).await?;
+        if unmigrated_miniblocks.is_empty() {
+            tracing::debug!("`fee_account_address` is migrated for chunk {chunk:?}");
+        }
+        self.unmigrated_miniblocks = unmigrated_miniblocks;
+        Ok(self.unmigrated_miniblocks.is_empty())
+    }
+
+    async fn migrate_chunk(
+        &mut self,
+        storage: &mut StorageProcessor<'_>,
+        chunk: RangeInclusive<MiniblockNumber>,
+    ) -> anyhow::Result<u64> {
+        let (Some(&first), Some(&last)) = (
+            self.unmigrated_miniblocks.first(),
+            self.unmigrated_miniblocks.last(),
+        ) else {
+            return Ok(0);
+        };
+        let unmigrated_span = first..=last;
+        tracing::debug!(
+            "Migrating `fee_account_address` for {} miniblocks in span {unmigrated_span:?} of chunk {chunk:?}",
+            self.unmigrated_miniblocks.len()
+        );
+
+        let rows_affected = if self.concurrency > 1 {
+            self.migrate_concurrently(&chunk).await?
+        } else {
+            copy_fee_addresses(storage, unmigrated_span, self.max_rows_per_statement)
+                .await
+                .with_context(|| format!("Failed migrating miniblocks chunk {chunk:?}"))?
+        };
+        tracing::debug!("Migrated {rows_affected} miniblocks in chunk {chunk:?}");
+
+        if self.verify_each_chunk {
+            #[allow(deprecated)]
+            let still_unmigrated = storage
+                .blocks_dal()
+                .unmigrated_miniblocks_in_range(chunk.clone(), None)
+                .await
+                .with_context(|| format!("Failed verifying miniblocks chunk {chunk:?}"))?;
+            anyhow::ensure!(
+                still_unmigrated.is_empty(),
+                "Verification failed for miniblocks chunk {chunk:?}: miniblocks {still_unmigrated:?} are not migrated"
+            );
+        }
+        Ok(rows_affected)
+    }
+
+    fn after_chunk(&mut self, outcome: &ChunkOutcome) -> anyhow::Result<bool> {
+        let chunk_end = *outcome.range.end();
+        self.miniblocks_affected += outcome.rows_affected;
+        self.chunks_processed += 1;
+        self.report_progress(&outcome.range);
+        if !outcome.was_migrated {
+            self.throughput
+                .observe(outcome.rows_affected, outcome.elapsed);
+            if let Some(rate) = self.throughput.current_rate().filter(|&rate| rate > 0.0) {
+                let remaining = u64::from(self.last_miniblock.0 - chunk_end.0);
+                let eta = Duration::from_secs_f64(remaining as f64 / rate);
+                tracing::debug!(
+                    "Fee address migration throughput: {rate:.1} miniblocks/s; ETA for remaining {remaining} miniblocks: {eta:?}"
+                );
+            }
+        }
+        if self.collect_timings {
+            self.chunk_timings.push(ChunkTiming {
+                range: outcome.range.clone(),
+                elapsed: outcome.elapsed,
+                miniblocks_affected: outcome.rows_affected,
+            });
+        }
+
+        if let Some(path) = self.checkpoint_path {
+            write_checkpoint(path, Checkpoint::InProgress(chunk_end + 1))?;
+        }
+
+        let reached_cap = self
+            .max_miniblocks
+            .map_or(false, |max| self.miniblocks_affected >= max);
+        if reached_cap {
+            tracing::info!(
+                "Cap on affected miniblocks is reached; fee address migration shutting down"
+            );
+        }
+        Ok(reached_cap)
+    }
+}
+
+/// Lists unmigrated miniblocks in `chunk`. To not fetch rows for chunks that are already migrated (which is the case
+/// for most chunks when the migration is resumed), this checks whether the chunk has any unmigrated miniblocks first.
+async fn list_unmigrated_miniblocks(
+    storage: &mut StorageProcessor<'_>,
+    chunk: RangeInclusive<MiniblockNumber>,
+) -> anyhow::Result<Vec<MiniblockNumber>> {
+    #[allow(deprecated)]
+    let has_unmigrated_miniblocks = storage
+        .blocks_dal()
+        .has_unmigrated_miniblocks(chunk.clone())
+        .await
+        .with_context(|| format!("Failed checking unmigrated miniblocks in chunk {chunk:?}"))?;
+    if !has_unmigrated_miniblocks {
+        return Ok(vec![]);
+    }
+
+    #[allow(deprecated)]
+    let unmigrated_miniblocks = storage
+        .blocks_dal()
+        .unmigrated_miniblocks_in_range(chunk.clone(), None)
+        .await
+        .with_context(|| format!("Failed getting unmigrated miniblocks in chunk {chunk:?}"))?;
+    Ok(unmigrated_miniblocks)
+}
+
+/// Copies fee addresses for miniblocks in `span`. If `max_rows_per_statement` is set, the span is processed
+/// by multiple statements, each updating at most the specified number of miniblocks.
+async fn copy_fee_addresses(
+    storage: &mut StorageProcessor<'_>,
+    span: RangeInclusive<MiniblockNumber>,
+    max_rows_per_statement: Option<u32>,
+) -> anyhow::Result<u64> {
+    let Some(max_rows) = max_rows_per_statement else {
+        #[allow(deprecated)]
+        let rows_affected = storage
+            .blocks_dal()
+            .copy_fee_account_address_for_miniblocks(span)
+            .await?;
+        return Ok(rows_affected);
+    };
+
+    let mut rows_affected = 0;
+    let mut start = *span.start();
+    loop {
+        #[allow(deprecated)]
+        let (statement_rows, next_miniblock) = storage
+            .blocks_dal()
+            .copy_fee_account_address_for_miniblocks_limited(start..=*span.end(), max_rows)
+            .await?;
+        rows_affected += statement_rows;
+        match next_miniblock {
+            Some(next_miniblock) => start = next_miniblock,
+            None => return Ok(rows_affected),
+        }
+    }
+}
+
+/// Extends `chunk_end` to the last miniblock of the L1 batch it belongs to, capped by `last_miniblock`.
+/// Miniblocks not included into a sealed L1 batch are returned as is.
+async fn align_to_l1_batch(
+    storage: &mut StorageProcessor<'_>,
+    chunk_end: MiniblockNumber,
+    last_miniblock: MiniblockNumber,
+) -> anyhow::Result<MiniblockNumber> {
+    let l1_batch = storage
+        .blocks_dal()
+        .get_batch_for_miniblock(chunk_end)
+        .await
+        .with_context(|| format!("Failed getting L1 batch for miniblock #{chunk_end}"))?;
+    let Some(l1_batch) = l1_batch else {
+        return Ok(chunk_end);
+    };
+    let (_, last_miniblock_in_batch) = storage
+        .blocks_dal()
+        .get_miniblock_range_of_l1_batch(l1_batch)
+        .await
+        .with_context(|| format!("Failed getting miniblock range for L1 batch #{l1_batch}"))?
+        .with_context(|| format!("L1 batch #{l1_batch} disappeared"))?;
+    Ok(last_miniblock_in_batch.min(last_miniblock))
+}
+
+/// Migration cursor persisted in the checkpoint file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Checkpoint {
+    /// Migration is in progress; it should be resumed from the specified miniblock.
+    InProgress(MiniblockNumber),
+    /// Migration is completed.
+    Completed,
+}
+
+impl Checkpoint {
+    const COMPLETED_MARKER: &'static str = "completed";
+}
+
+/// Reads the migration checkpoint from the specified file. Returns `None` if the file doesn't exist.
+fn read_checkpoint(path: &Path) -> anyhow::Result<Option<Checkpoint>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed reading checkpoint file {path:?}"))
+        }
+    };
+    let contents = contents.trim();
+    if contents == Checkpoint::COMPLETED_MARKER {
+        return Ok(Some(Checkpoint::Completed));
+    }
+    let checkpoint = contents
+        .parse::<u32>()
+        .with_context(|| format!("Malformed checkpoint file {path:?}: {contents:?}"))?;
+    Ok(Some(Checkpoint::InProgress(MiniblockNumber(checkpoint))))
+}
+
+/// Atomically writes the migration checkpoint to the specified file by writing it to a temporary file first
+/// and then renaming the temporary file.
+fn write_checkpoint(path: &Path, checkpoint: Checkpoint) -> anyhow::Result<()> {
+    let contents = match checkpoint {
+        Checkpoint::InProgress(next_miniblock) => next_miniblock.0.to_string(),
+        Checkpoint::Completed => Checkpoint::COMPLETED_MARKER.to_owned(),
+    };
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, contents)
+        .with_context(|| format!("Failed writing temporary checkpoint file {tmp_path:?}"))?;
+    fs::rename(&tmp_path, path)
+        .with_context(|| format!("Failed renaming checkpoint file {tmp_path:?} to {path:?}"))?;
+    Ok(())
+}
+
+/// Removes the temporary checkpoint file left over if the process was terminated while writing the checkpoint.
+fn remove_stale_checkpoint_files(path: &Path) -> anyhow::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    match fs::remove_file(&tmp_path) {
+        Ok(()) => {
+            tracing::info!("Removed stale temporary checkpoint file {tmp_path:?}");
+            Ok(())
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => {
+            Err(err).with_context(|| format!("Failed removing stale checkpoint file {tmp_path:?}"))
+        }
+    }
+}
+
+#[allow(deprecated)]
+async fn is_fee_address_migrated(
+    storage: &mut StorageProcessor<'_>,
+    miniblock: MiniblockNumber,
+) -> anyhow::Result<bool> {
+    storage
+        .blocks_dal()
+        .is_fee_address_migrated(miniblock)
+        .await
+        .with_context(|| format!("Failed getting fee address for miniblock #{miniblock}"))?
+        .with_context(|| format!("Miniblock #{miniblock} disappeared"))
+}
+
+#[cfg(test)]
+mod tests {
+    use test_casing::{test_casing, Product};
+    use zksync_contracts::BaseSystemContractsHashes;
+    use zksync_types::{
+        block::L1BatchHeader, Address, L1BatchNumber, ProtocolVersion, ProtocolVersionId,
+    };
+
+    use super::*;
+    use crate::utils::testonly::create_miniblock;
+
+    async fn prepare_storage(storage: &mut StorageProcessor<'_>) {
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        for number in 0..5 {
+            let fee_address = Address::from_low_u64_be(u64::from(number) + 1);
+            seal_l1_batch_with_fee_address(storage, number, fee_address).await;
+        }
+    }
+
+    /// Inserts a miniblock and an L1 batch with the specified number, with the fee address set only for the L1 batch
+    /// (i.e., as if the miniblock was sealed before the fee address was stored for miniblocks).
+    async fn seal_l1_batch_with_fee_address(
+        storage: &mut StorageProcessor<'_>,
+        number: u32,
+        fee_address: Address,
+    ) {
+        storage
+            .blocks_dal()
+            .insert_miniblock(&create_miniblock(number))
+            .await
+            .unwrap();
+        let l1_batch = L1BatchHeader::new(
+            L1BatchNumber(number),
+            number.into(),
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::latest(),
+        );
+        storage
+            .blocks_dal()
+            .insert_mock_l1_batch(&l1_batch)
+            .await
+            .unwrap();
+        #[allow(deprecated)]
+        storage
+            .blocks_dal()
+            .set_l1_batch_fee_address(l1_batch.number, fee_address)
+            .await
+            .unwrap();
+        storage
+            .blocks_dal()
+            .mark_miniblocks_as_executed_in_l1_batch(l1_batch.number)
+            .await
+            .unwrap();
+    }
+
+    // Setters for parameters that are only tuned in tests.
+    impl MigrationConfigBuilder {
+        /// Sets the first miniblock to migrate.
+        fn from_miniblock(mut self, from_miniblock: MiniblockNumber) -> Self {
+            self.config.from_miniblock = from_miniblock;
+            self
+        }
+
+        /// Sets the (initial) number of miniblocks in a chunk.
+        fn chunk_size(mut self, chunk_size: u32) -> Self {
+            self.config.chunk_size = chunk_size;
+            self
+        }
+
+        /// Enables adapting the chunk size to the chunk processing time.
+        fn adaptive_chunk_size(mut self, params: AdaptiveChunkSize) -> Self {
+            self.config.adaptive_chunk_size = Some(params);
+            self
+        }
+
+        /// Sets the number of chunks migrated in parallel.
+        fn concurrency(mut self, concurrency: usize) -> Self {
+            self.config.concurrency = concurrency;
+            self
+        }
+
+        /// Limits the number of miniblocks updated by a single DB statement.
+        fn max_rows_per_statement(mut self, max_rows: u32) -> Self {
+            self.config.max_rows_per_statement = Some(max_rows);
+            self
+        }
+
+        /// Sets the delay between migrated chunks.
+        fn backoff(mut self, backoff: BackoffPolicy) -> Self {
+            self.config.backoff = backoff;
+            self
+        }
+
+        /// Sets whether chunks should be aligned to L1 batch boundaries.
+        fn align_to_l1_batches(mut self, align: bool) -> Self {
+            self.config.align_to_l1_batches = align;
+            self
+        }
+
+        /// Stops the migration once it has affected the specified number of miniblocks.
+        fn max_miniblocks(mut self, max_miniblocks: u64) -> Self {
+            self.config.max_miniblocks = Some(max_miniblocks);
+            self
+        }
+
+        /// Sets whether the `miniblocks` table should be analyzed after the migration.
+        fn analyze_after(mut self, analyze_after: bool) -> Self {
+            self.config.analyze_after = analyze_after;
+            self
+        }
+
+        /// Sets whether per-chunk timings should be collected.
+        fn collect_timings(mut self, collect_timings: bool) -> Self {
+            self.config.collect_timings = collect_timings;
+            self
+        }
+
+        /// Sets whether each chunk should be verified after it's migrated.
+        fn verify_each_chunk(mut self, verify_each_chunk: bool) -> Self {
+            self.config.verify_each_chunk = verify_each_chunk;
+            self
+        }
+
+        /// Sets the path to the checkpoint file used to resume the migration.
+        fn checkpoint_path(mut self, path: PathBuf) -> Self {
+            self.config.checkpoint_path = Some(path);
+            self
+        }
+
+        /// Sets how the migration reacts to the stop signal.
+        fn stop_mode(mut self, stop_mode: StopMode) -> Self {
+            self.config.stop_mode = stop_mode;
+            self
+        }
+    }
+
+    /// Returns a migration config builder without delays between chunks.
+    fn test_config(last_miniblock: MiniblockNumber, chunk_size: u32) -> MigrationConfigBuilder {
+        MigrationConfig::builder(last_miniblock)
+            .chunk_size(chunk_size)
+            .backoff(BackoffPolicy::None)
+    }
+
+    async fn assert_migration(storage: &mut StorageProcessor<'_>) {
+        for number in 0..5 {
+            assert!(is_fee_address_migrated(storage, MiniblockNumber(number))
+                .await
+                .unwrap());
+
+            let fee_address = storage
+                .blocks_dal()
+                .get_fee_address_for_miniblock(MiniblockNumber(number))
+                .await
+                .unwrap()
+                .expect("no fee address");
+            let expected_address = Address::from_low_u64_be(u64::from(number) + 1);
+            assert_eq!(fee_address, expected_address);
+        }
+    }
+
+    #[test]
+    fn default_migration_config() {
+        let config = MigrationConfig::builder(MiniblockNumber(42)).build();
+        assert_eq!(config.from_miniblock, MiniblockNumber(0));
+        assert_eq!(config.last_miniblock, MiniblockNumber(42));
+        assert_eq!(config.chunk_size, 100_000);
+        assert!(config.adaptive_chunk_size.is_none());
+        assert_eq!(config.concurrency, 1);
+        assert_eq!(config.max_rows_per_statement, None);
+        assert_eq!(config.backoff, BackoffPolicy::Fixed(Duration::from_secs(1)));
+        assert!(!config.align_to_l1_batches);
+        assert_eq!(config.max_miniblocks, None);
+        assert!(!config.analyze_after);
+        assert!(!config.collect_timings);
+        assert!(!config.verify_each_chunk);
+        assert_eq!(config.checkpoint_path, None);
+        assert_eq!(config.resume_from_batch, None);
+        assert_eq!(config.stop_mode, StopMode::AfterChunk);
+    }
+
+    #[tokio::test]
+    async fn migration_with_default_config() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        drop(storage);
+
+        // Uses the config produced by `MigrationConfig::builder()` without any changes.
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        migrate_miniblocks(
+            pool.clone(),
+            MiniblockNumber(4),
+            None,
+            stop_receiver.clone(),
+        )
+        .await
+        .unwrap();
+        let mut storage = pool.access_storage().await.unwrap();
+        assert_migration(&mut storage).await;
+        drop(storage);
+
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            MigrationConfig::builder(MiniblockNumber(4)).build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.miniblocks_affected, 0);
+        assert_eq!(result.chunks_processed, 1);
+        assert!(!result.miniblocks_table_analyzed);
+    }
+
+    #[test_casing(3, [1, 2, 3])]
+    #[tokio::test]
+    async fn migration_basics(chunk_size: u32) {
+        // Replicate providing a pool with a single connection.
+        let pool = ConnectionPool::constrained_test_pool(1).await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        drop(storage);
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), chunk_size).build(),
+            stop_receiver.clone(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.miniblocks_affected, 5);
+        let expected_chunks = 5_u64.div_ceil(chunk_size.into());
+        assert_eq!(result.chunks_processed, expected_chunks);
+
+        // Check that all blocks are migrated.
+        let mut storage = pool.access_storage().await.unwrap();
+        assert_migration(&mut storage).await;
+        drop(storage);
+
+        // Check that migration can run again w/o returning an error, hanging up etc.
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), chunk_size).build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.miniblocks_affected, 0);
+        // Already migrated chunks should still be reported.
+        assert_eq!(result.chunks_processed, expected_chunks);
+    }
+
+    async fn raw_fee_addresses(storage: &mut StorageProcessor<'_>) -> Vec<Address> {
+        let mut addresses = vec![];
+        for number in 0..5 {
+            let is_migrated = is_fee_address_migrated(storage, MiniblockNumber(number))
+                .await
+                .unwrap();
+            let address = storage
+                .blocks_dal()
+                .get_fee_address_for_miniblock(MiniblockNumber(number))
+                .await
+                .unwrap()
+                .expect("no fee address");
+            addresses.push(if is_migrated {
+                address
+            } else {
+                Address::zero()
+            });
+        }
+        addresses
+    }
+
+    #[tokio::test]
+    async fn dry_run_does_not_change_storage() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        let addresses_before = raw_fee_addresses(&mut storage).await;
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let report = dry_run_migration(pool.clone(), MiniblockNumber(4), 2, stop_receiver)
+            .await
+            .unwrap();
+        assert_eq!(
+            report,
+            DryRunReport {
+                unmigrated_chunks: vec![
+                    MiniblockNumber(0)..=MiniblockNumber(1),
+                    MiniblockNumber(2)..=MiniblockNumber(3),
+                    MiniblockNumber(4)..=MiniblockNumber(4),
+                ],
+                unmigrated_miniblocks: 5,
+                stopped: false,
+            }
+        );
+        assert_eq!(raw_fee_addresses(&mut storage).await, addresses_before);
+
+        // Migrate all miniblocks except for #2.
+        for chunk in [
+            MiniblockNumber(0)..=MiniblockNumber(1),
+            MiniblockNumber(3)..=MiniblockNumber(4),
+        ] {
+            #[allow(deprecated)]
+            storage
+                .blocks_dal()
+                .copy_fee_account_address_for_miniblocks(chunk)
+                .await
+                .unwrap();
+        }
+        let addresses_before = raw_fee_addresses(&mut storage).await;
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let report = dry_run_migration(pool.clone(), MiniblockNumber(4), 2, stop_receiver)
+            .await
+            .unwrap();
+        assert_eq!(
+            report.unmigrated_chunks,
+            [MiniblockNumber(2)..=MiniblockNumber(3)]
+        );
+        assert_eq!(report.unmigrated_miniblocks, 1);
+        assert_eq!(raw_fee_addresses(&mut storage).await, addresses_before);
+    }
+
+    #[tokio::test]
+    async fn stopping_dry_run() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        drop(storage);
+
+        let (_stop_sender, stop_receiver) = watch::channel(true); // signal stop right away
+        let report = dry_run_migration(pool, MiniblockNumber(4), 2, stop_receiver)
+            .await
+            .unwrap();
+        assert!(report.stopped);
+        assert_eq!(
+            report.unmigrated_chunks,
+            [MiniblockNumber(0)..=MiniblockNumber(1)]
+        );
+        assert_eq!(report.unmigrated_miniblocks, 2);
+    }
+
+    #[tokio::test]
+    async fn verifying_migration() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let report = verify_migration(pool.clone(), MiniblockNumber(4), 2, stop_receiver.clone())
+            .await
+            .unwrap();
+        // Unmigrated chunks are not inconsistent.
+        assert_eq!(report, VerificationReport::default());
+
+        // Migrate all miniblocks except for #3, which is in the middle of the chunk `2..=3`.
+        for chunk in [
+            MiniblockNumber(0)..=MiniblockNumber(2),
+            MiniblockNumber(4)..=MiniblockNumber(4),
+        ] {
+            #[allow(deprecated)]
+            storage
+                .blocks_dal()
+                .copy_fee_account_address_for_miniblocks(chunk)
+                .await
+                .unwrap();
+        }
+        let addresses_before = raw_fee_addresses(&mut storage).await;
+        let report = verify_migration(pool.clone(), MiniblockNumber(4), 2, stop_receiver)
+            .await
+            .unwrap();
+        assert_eq!(
+            report.inconsistent_chunks,
+            [(MiniblockNumber(2)..=MiniblockNumber(3), 1)]
+        );
+        assert_eq!(report.migrated_chunks, 3);
+        assert!(!report.stopped);
+        assert_eq!(raw_fee_addresses(&mut storage).await, addresses_before);
+
+        // Once the chunk is fully migrated, verification succeeds.
+        #[allow(deprecated)]
+        storage
+            .blocks_dal()
+            .copy_fee_account_address_for_miniblocks(MiniblockNumber(3)..=MiniblockNumber(3))
+            .await
+            .unwrap();
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let report = verify_migration(pool, MiniblockNumber(4), 2, stop_receiver)
+            .await
+            .unwrap();
+        assert_eq!(
+            report,
+            VerificationReport {
+                inconsistent_chunks: vec![],
+                migrated_chunks: 3,
+                stopped: false,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn migrating_partially_migrated_chunk() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        // Migrate all miniblocks except for #2.
+        for chunk in [
+            MiniblockNumber(0)..=MiniblockNumber(1),
+            MiniblockNumber(3)..=MiniblockNumber(4),
+        ] {
+            #[allow(deprecated)]
+            storage
+                .blocks_dal()
+                .copy_fee_account_address_for_miniblocks(chunk)
+                .await
+                .unwrap();
+        }
+        drop(storage);
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 5).build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.miniblocks_affected, 1);
+        let mut storage = pool.access_storage().await.unwrap();
+        assert_migration(&mut storage).await;
+    }
+
+    #[tokio::test]
+    async fn verifying_each_chunk() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        drop(storage);
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 2)
+                .verify_each_chunk(true)
+                .build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.miniblocks_affected, 5);
+        let mut storage = pool.access_storage().await.unwrap();
+        assert_migration(&mut storage).await;
+    }
+
+    #[tokio::test]
+    async fn verifying_each_chunk_with_corrupted_chunk() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        // Corrupt the source data for miniblock #3, so that it cannot be migrated.
+        #[allow(deprecated)]
+        storage
+            .blocks_dal()
+            .set_l1_batch_fee_address(L1BatchNumber(3), Address::zero())
+            .await
+            .unwrap();
+        drop(storage);
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let err = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 2)
+                .verify_each_chunk(true)
+                .build(),
+            stop_receiver.clone(),
+        )
+        .await
+        .unwrap_err();
+        let err = format!("{err:#}");
+        assert!(
+            err.contains("Verification failed") && err.contains("[MiniblockNumber(3)]"),
+            "{err}"
+        );
+
+        // The migration must have been aborted after the corrupted chunk.
+        let mut storage = pool.access_storage().await.unwrap();
+        for number in 0..=2 {
+            assert!(
+                is_fee_address_migrated(&mut storage, MiniblockNumber(number))
+                    .await
+                    .unwrap()
+            );
+        }
+        assert!(!is_fee_address_migrated(&mut storage, MiniblockNumber(4))
+            .await
+            .unwrap());
+        drop(storage);
+
+        // Without verification, the corrupted miniblock is silently left unmigrated.
+        migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 2).build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+        let mut storage = pool.access_storage().await.unwrap();
+        assert!(!is_fee_address_migrated(&mut storage, MiniblockNumber(3))
+            .await
+            .unwrap());
+        assert!(is_fee_address_migrated(&mut storage, MiniblockNumber(4))
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn checking_schema_before_migration() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        ensure_schema_is_up_to_date(&mut storage, REQUIRED_COLUMNS)
+            .await
+            .unwrap();
+
+        // Simulate an outdated schema by requiring a non-existing column.
+        let columns = [
+            ("miniblocks", "fee_account_address"),
+            ("miniblocks", "non_existing_column"),
+        ];
+        let err = ensure_schema_is_up_to_date(&mut storage, &columns)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("schema is not up to date"), "{err}");
+        assert!(err.contains("`miniblocks.non_existing_column`"), "{err}");
+        assert!(err.contains("run migrations first"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn migration_with_adaptive_chunk_size() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        drop(storage);
+
+        let adaptive_chunk_size = AdaptiveChunkSize {
+            target_duration: Duration::from_secs(100),
+            min_chunk_size: 1,
+            max_chunk_size: 4,
+        };
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 1)
+                .adaptive_chunk_size(adaptive_chunk_size)
+                .collect_timings(true)
+                .build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.miniblocks_affected, 5);
+        let chunks: Vec<_> = result
+            .chunk_timings
+            .iter()
+            .map(|timing| timing.range.clone())
+            .collect();
+        // Chunks are fast, so the chunk size should grow.
+        assert_eq!(
+            chunks,
+            [
+                MiniblockNumber(0)..=MiniblockNumber(0),
+                MiniblockNumber(1)..=MiniblockNumber(2),
+                MiniblockNumber(3)..=MiniblockNumber(4),
+            ]
+        );
+        let mut storage = pool.access_storage().await.unwrap();
+        assert_migration(&mut storage).await;
+    }
+
+    #[tokio::test]
+    async fn collecting_chunk_timings() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        drop(storage);
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 2)
+                .collect_timings(true)
+                .build(),
+            stop_receiver.clone(),
+        )
+        .await
+        .unwrap();
+
+        let chunks: Vec<_> = result
+            .chunk_timings
+            .iter()
+            .map(|timing| (timing.range.clone(), timing.miniblocks_affected))
+            .collect();
+        assert_eq!(
+            chunks,
+            [
+                (MiniblockNumber(0)..=MiniblockNumber(1), 2),
+                (MiniblockNumber(2)..=MiniblockNumber(3), 2),
+                (MiniblockNumber(4)..=MiniblockNumber(4), 1),
+            ]
+        );
+        let total_chunk_time: Duration = result.chunk_timings.iter().map(|t| t.elapsed).sum();
+        assert!(total_chunk_time <= result.elapsed);
+        let report = result.to_json_report();
+        assert_eq!(report["chunk_timings"].as_array().unwrap().len(), 3);
+
+        // Timings are not collected by default.
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 2).build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+        assert!(result.chunk_timings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn reporting_current_migration_rate() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        drop(storage);
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 2).build(),
+            stop_receiver.clone(),
+        )
+        .await
+        .unwrap();
+        let current_rate = result.current_rate.expect("no migration rate");
+        assert!(current_rate > 0.0, "{current_rate}");
+        let report = result.to_json_report();
+        assert_eq!(report["current_rate"], current_rate);
+
+        // All chunks are already migrated, so there are no throughput data.
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 2).build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.current_rate, None);
+    }
+
+    #[test_casing(6, Product(([1, 2, 3], [StopMode::AfterChunk, StopMode::BeforeWrite])))]
+    #[tokio::test]
+    async fn stopping_and_resuming_migration(chunk_size: u32, stop_mode: StopMode) {
+        let pool = ConnectionPool::constrained_test_pool(1).await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        drop(storage);
+
+        let (_stop_sender, stop_receiver) = watch::channel(true); // signal stop right away
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), chunk_size)
+                .backoff(BackoffPolicy::Fixed(Duration::from_secs(1_000)))
+                .stop_mode(stop_mode)
+                .build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+
+        // Migration should stop after a single chunk, or before migrating the first chunk.
+        let migrated_before_stop = match stop_mode {
+            StopMode::AfterChunk => u64::from(chunk_size),
+            StopMode::BeforeWrite => 0,
+        };
+        assert_eq!(result.miniblocks_affected, migrated_before_stop);
+        assert_eq!(result.miniblocks_remaining, 5 - migrated_before_stop);
+        let expected_resume_from = MiniblockNumber(migrated_before_stop as u32);
+        assert_eq!(result.resume_from, Some(expected_resume_from));
+
+        // Check that migration resumes from the same point.
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), chunk_size)
+                .stop_mode(stop_mode)
+                .build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.miniblocks_affected, 5 - migrated_before_stop);
+        assert_eq!(result.miniblocks_remaining, 0);
+        let mut storage = pool.access_storage().await.unwrap();
+        assert_migration(&mut storage).await;
+    }
+
+    #[test_casing(3, [2, 3, 10])]
+    #[tokio::test]
+    async fn migration_with_concurrency(concurrency: usize) {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 1)
+                .concurrency(concurrency)
+                .build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.miniblocks_affected, 5);
+        assert_eq!(result.miniblocks_remaining, 0);
+        assert_migration(&mut storage).await;
+    }
+
+    #[tokio::test]
+    async fn stopping_and_resuming_migration_with_concurrency() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        drop(storage);
+
+        let (_stop_sender, stop_receiver) = watch::channel(true); // signal stop right away
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 1)
+                .concurrency(2)
+                .backoff(BackoffPolicy::Fixed(Duration::from_secs(1_000)))
+                .build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+
+        // Migration should stop after a single group of 2 chunks.
+        assert_eq!(result.miniblocks_affected, 2);
+        assert_eq!(result.miniblocks_remaining, 3);
+        assert_eq!(result.resume_from, Some(MiniblockNumber(2)));
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 1).concurrency(2).build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.miniblocks_affected, 3);
+        assert_eq!(result.miniblocks_remaining, 0);
+        let mut storage = pool.access_storage().await.unwrap();
+        assert_migration(&mut storage).await;
+    }
+
+    #[test_casing(4, Product(([1, 2], [1, 3])))]
+    #[tokio::test]
+    async fn migration_with_limited_statements(concurrency: usize, max_rows: u32) {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 5)
+                .concurrency(concurrency)
+                .max_rows_per_statement(max_rows)
+                .build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.miniblocks_affected, 5);
+        assert_eq!(result.miniblocks_remaining, 0);
+        assert_migration(&mut storage).await;
+    }
+
+    #[tokio::test]
+    async fn migration_with_zero_concurrency() {
+        let pool = ConnectionPool::test_pool().await;
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let err = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 1).concurrency(0).build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("Concurrency must be positive"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn migration_with_miniblocks_cap() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        drop(storage);
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 2).max_miniblocks(3).build(),
+            stop_receiver.clone(),
+        )
+        .await
+        .unwrap();
+        // The cap is reached after the second chunk `2..=3`.
+        assert_eq!(result.miniblocks_affected, 4);
+        assert_eq!(result.miniblocks_remaining, 1);
+        assert_eq!(result.resume_from, Some(MiniblockNumber(4)));
+
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 2).max_miniblocks(3).build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.miniblocks_affected, 1);
+        assert_eq!(result.miniblocks_remaining, 0);
+        assert_eq!(result.resume_from, None);
+        let mut storage = pool.access_storage().await.unwrap();
+        assert_migration(&mut storage).await;
+    }
+
+    #[tokio::test]
+    async fn estimating_write_amplification() {
+        let pool = ConnectionPool::test_pool().await;
+        assert_eq!(estimate_write_amplification(&pool).await.unwrap(), 0);
+
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        // Partially migrate miniblocks so that the estimate differs from the total number of miniblocks.
+        #[allow(deprecated)]
+        storage
+            .blocks_dal()
+            .copy_fee_account_address_for_miniblocks(MiniblockNumber(1)..=MiniblockNumber(2))
+            .await
+            .unwrap();
+        drop(storage);
+
+        let estimate = estimate_write_amplification(&pool).await.unwrap();
+        assert_eq!(estimate, 3);
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 2).build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.miniblocks_affected, estimate);
+        assert_eq!(estimate_write_amplification(&pool).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn estimating_migration_cost() {
+        let pool = ConnectionPool::test_pool().await;
+        let estimate = estimate_migration_cost(&pool).await.unwrap();
+        assert_eq!(
+            estimate,
+            MigrationEstimate {
+                total_miniblocks: 0,
+                unmigrated_miniblocks: 0,
+            }
+        );
+
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        drop(storage);
+        let estimate = estimate_migration_cost(&pool).await.unwrap();
+        assert_eq!(
+            estimate,
+            MigrationEstimate {
+                total_miniblocks: 5,
+                unmigrated_miniblocks: 5,
+            }
+        );
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(1), 1).build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+        let estimate = estimate_migration_cost(&pool).await.unwrap();
+        assert_eq!(
+            estimate,
+            MigrationEstimate {
+                total_miniblocks: 5,
+                unmigrated_miniblocks: 3,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn estimating_chunk_memory() {
+        let pool = ConnectionPool::test_pool().await;
+        assert_eq!(estimate_chunk_memory(&pool, 100).await.unwrap(), 0);
+
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        drop(storage);
+
+        let estimate = estimate_chunk_memory(&pool, 1).await.unwrap();
+        // A miniblock row contains several hashes and addresses, but doesn't contain large blobs.
+        assert!((100..10_000).contains(&estimate), "{estimate}");
+        assert_eq!(
+            estimate_chunk_memory(&pool, 100).await.unwrap(),
+            estimate * 100
+        );
+    }
+
+    #[tokio::test]
+    async fn analyzing_table_after_migration() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        drop(storage);
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(2), 2).build(),
+            stop_receiver.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.miniblocks_affected, 3);
+        assert!(!result.miniblocks_table_analyzed);
+
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 2)
+                .analyze_after(true)
+                .build(),
+            stop_receiver.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.miniblocks_affected, 2);
+        assert!(result.miniblocks_table_analyzed);
+
+        // The table shouldn't be analyzed if the migration has no effect.
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 2)
+                .analyze_after(true)
+                .build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.miniblocks_affected, 0);
+        assert!(!result.miniblocks_table_analyzed);
+    }
 
-    async fn prepare_storage(storage: &mut StorageProcessor<'_>) {
+    #[tokio::test]
+    async fn migration_with_chunks_aligned_to_l1_batches() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
         storage
             .protocol_versions_dal()
             .save_protocol_version_with_tx(ProtocolVersion::default())
             .await;
-        for number in 0..5 {
-            let miniblock = create_miniblock(number);
-            storage
-                .blocks_dal()
-                .insert_miniblock(&miniblock)
-                .await
-                .unwrap();
-
+        // L1 batches #0, #1 and #2 contain miniblocks 0, 1..=3 and 4..=5 respectively.
+        let l1_batch_ranges = [0..=0, 1..=3, 4..=5];
+        for (l1_batch_number, miniblock_range) in l1_batch_ranges.into_iter().enumerate() {
+            for number in miniblock_range {
+                storage
+                    .blocks_dal()
+                    .insert_miniblock(&create_miniblock(number))
+                    .await
+                    .unwrap();
+            }
             let l1_batch = L1BatchHeader::new(
-                L1BatchNumber(number),
-                number.into(),
+                L1BatchNumber(l1_batch_number as u32),
+                l1_batch_number as u64,
                 BaseSystemContractsHashes::default(),
                 ProtocolVersionId::latest(),
             );
@@ -183,10 +2255,7 @@ mod tests {
             #[allow(deprecated)]
             storage
                 .blocks_dal()
-                .set_l1_batch_fee_address(
-                    l1_batch.number,
-                    Address::from_low_u64_be(u64::from(number) + 1),
-                )
+                .set_l1_batch_fee_address(l1_batch.number, Address::repeat_byte(1))
                 .await
                 .unwrap();
             storage
@@ -195,30 +2264,355 @@ mod tests {
                 .await
                 .unwrap();
         }
+        drop(storage);
+
+        let (_stop_sender, stop_receiver) = watch::channel(true); // signal stop right away
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(5), 2)
+                .backoff(BackoffPolicy::Fixed(Duration::from_secs(1_000)))
+                .align_to_l1_batches(true)
+                .build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+        // The first chunk `0..=1` must be extended to the end of L1 batch #1.
+        assert_eq!(result.miniblocks_affected, 4);
+        assert_eq!(result.miniblocks_remaining, 2);
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(5), 1)
+                .align_to_l1_batches(true)
+                .build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+        // The first non-migrated chunk `4..=4` must be extended to the end of L1 batch #2.
+        assert_eq!(result.miniblocks_affected, 2);
+        assert_eq!(result.miniblocks_remaining, 0);
     }
 
-    async fn assert_migration(storage: &mut StorageProcessor<'_>) {
+    #[tokio::test]
+    async fn migration_within_transaction() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+
+        let mut transaction = storage.start_transaction().await.unwrap();
+        let rows_affected = migrate_miniblocks_in_txn(&mut transaction, MiniblockNumber(4))
+            .await
+            .unwrap();
+        assert_eq!(rows_affected, 5);
+        assert_migration(&mut transaction).await;
+        drop(transaction); // Roll back the transaction
+
         for number in 0..5 {
-            assert!(is_fee_address_migrated(storage, MiniblockNumber(number))
-                .await
-                .unwrap());
+            assert!(
+                !is_fee_address_migrated(&mut storage, MiniblockNumber(number))
+                    .await
+                    .unwrap()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn migration_on_empty_storage() {
+        let pool = ConnectionPool::test_pool().await;
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let err = migrate_miniblocks_inner(
+            pool,
+            test_config(MiniblockNumber(0), 10).build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(
+            err.downcast_ref::<StorageNotInitialized>().is_some(),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn estimating_remaining_migration_time() {
+        let output = MigrationOutput {
+            miniblocks_affected: 100,
+            miniblocks_remaining: 50,
+            resume_from: Some(MiniblockNumber(100)),
+            miniblocks_table_analyzed: false,
+            chunks_processed: 10,
+            elapsed: Duration::from_secs(10),
+            current_rate: None,
+            chunk_timings: vec![],
+        };
+        assert_eq!(output.estimated_remaining(), Some(Duration::from_secs(5)));
+
+        let output = MigrationOutput {
+            miniblocks_remaining: 0,
+            ..output
+        };
+        assert_eq!(output.estimated_remaining(), Some(Duration::ZERO));
+
+        let output = MigrationOutput {
+            miniblocks_remaining: 50,
+            ..MigrationOutput::default()
+        };
+        assert_eq!(output.estimated_remaining(), None);
+    }
+
+    #[test]
+    fn formatting_migration_summary() {
+        assert_eq!(format_with_thousands_separators(0), "0");
+        assert_eq!(format_with_thousands_separators(999), "999");
+        assert_eq!(format_with_thousands_separators(1_000), "1,000");
+        assert_eq!(format_with_thousands_separators(1_234_567), "1,234,567");
+        assert_eq!(format_duration(Duration::from_millis(5_900)), "5s");
+        assert_eq!(format_duration(Duration::from_secs(754)), "12m34s");
+        assert_eq!(format_duration(Duration::from_secs(3_723)), "1h02m03s");
+
+        let output = MigrationOutput {
+            miniblocks_affected: 1_234_567,
+            elapsed: Duration::from_secs(754),
+            ..MigrationOutput::default()
+        };
+        assert_eq!(
+            output.summary_line(),
+            "fee address migration: 1,234,567 miniblocks affected in 12m34s (completed)"
+        );
+
+        let output = MigrationOutput {
+            miniblocks_remaining: 50_000,
+            resume_from: Some(MiniblockNumber(1_234_567)),
+            ..output
+        };
+        assert_eq!(
+            output.summary_line(),
+            "fee address migration: 1,234,567 miniblocks affected in 12m34s \
+             (stopped; 50,000 miniblocks remaining)"
+        );
+    }
+
+    #[test]
+    fn computing_windowed_throughput() {
+        let mut tracker = ThroughputTracker::new(3);
+        assert_eq!(tracker.current_rate(), None);
+
+        tracker.observe(100, Duration::from_secs(1));
+        assert_eq!(tracker.current_rate(), Some(100.0));
+        tracker.observe(200, Duration::from_secs(3));
+        assert_eq!(tracker.current_rate(), Some(75.0));
+        tracker.observe(0, Duration::from_secs(1));
+        assert_eq!(tracker.current_rate(), Some(60.0));
+
+        // The first chunk should be evicted from the window.
+        tracker.observe(500, Duration::from_secs(5));
+        assert_eq!(tracker.current_rate(), Some(700.0 / 9.0));
+        tracker.observe(10, Duration::ZERO);
+        tracker.observe(10, Duration::ZERO);
+        assert_eq!(tracker.current_rate(), Some(520.0 / 5.0));
+        tracker.observe(10, Duration::ZERO);
+        assert_eq!(tracker.current_rate(), None);
+    }
+
+    #[test]
+    fn migration_output_json_report() {
+        let output = MigrationOutput {
+            miniblocks_affected: 100,
+            miniblocks_remaining: 50,
+            resume_from: Some(MiniblockNumber(100)),
+            miniblocks_table_analyzed: false,
+            chunks_processed: 2,
+            elapsed: Duration::from_millis(1_500),
+            current_rate: Some(80.0),
+            chunk_timings: vec![],
+        };
+        let report = output.to_json_report();
+        assert_eq!(
+            report,
+            serde_json::json!({
+                "miniblocks_affected": 100,
+                "miniblocks_remaining": 50,
+                "resume_from": 100,
+                "miniblocks_table_analyzed": false,
+                "chunks_processed": 2,
+                "elapsed": { "secs": 1, "nanos": 500_000_000 },
+                "current_rate": 80.0,
+            })
+        );
+
+        let report = MigrationOutput::default().to_json_report();
+        assert_eq!(report["resume_from"], serde_json::Value::Null);
+    }
+
+    #[test_casing(3, [1, 2, 3])]
+    #[tokio::test]
+    async fn new_blocks_added_during_migration(chunk_size: u32) {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+
+        let (_stop_sender, stop_receiver) = watch::channel(true); // signal stop right away
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), chunk_size)
+                .backoff(BackoffPolicy::Fixed(Duration::from_secs(1_000)))
+                .build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+
+        // Migration should stop after a single chunk.
+        assert_eq!(result.miniblocks_affected, u64::from(chunk_size));
+
+        // Insert a new miniblock to the storage with a defined fee account address.
+        let mut miniblock = create_miniblock(5);
+        miniblock.fee_account_address = Address::repeat_byte(1);
+        storage
+            .blocks_dal()
+            .insert_miniblock(&miniblock)
+            .await
+            .unwrap();
+
+        // Resume the migration.
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(5), chunk_size).build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+
+        // The new miniblock should not be affected.
+        assert_eq!(result.miniblocks_affected, 5 - u64::from(chunk_size));
+        assert_migration(&mut storage).await;
+    }
+
+    #[tokio::test]
+    async fn blocks_sealed_during_migration_are_not_in_scope() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let migration_task = tokio::spawn(migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(u32::MAX - 1), 1)
+                .backoff(BackoffPolicy::Fixed(Duration::from_millis(10)))
+                .build(),
+            stop_receiver,
+        ));
+
+        // Wait until the migration has started, i.e., its scope is fixed.
+        while !is_fee_address_migrated(&mut storage, MiniblockNumber(0))
+            .await
+            .unwrap()
+        {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // Emulate block production continuing during the migration.
+        seal_l1_batch_with_fee_address(&mut storage, 5, Address::repeat_byte(1)).await;
+
+        let result = migration_task.await.unwrap().unwrap();
+        assert_eq!(result.miniblocks_affected, 5);
+        assert_eq!(result.miniblocks_remaining, 0);
+        assert_migration(&mut storage).await;
+        // The newly sealed miniblock should not be affected.
+        assert!(!is_fee_address_migrated(&mut storage, MiniblockNumber(5))
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn migrating_to_tip() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        migrate_miniblocks_to_tip(pool.clone(), stop_receiver.clone())
+            .await
+            .unwrap();
+        assert_migration(&mut storage).await;
+
+        // Miniblocks sealed after the migration should be processed by the next run.
+        seal_l1_batch_with_fee_address(&mut storage, 5, Address::repeat_byte(1)).await;
+        assert!(!is_fee_address_migrated(&mut storage, MiniblockNumber(5))
+            .await
+            .unwrap());
+        migrate_miniblocks_to_tip(pool.clone(), stop_receiver)
+            .await
+            .unwrap();
+        assert!(is_fee_address_migrated(&mut storage, MiniblockNumber(5))
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn migrating_to_tip_on_empty_storage() {
+        let pool = ConnectionPool::test_pool().await;
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let err = migrate_miniblocks_to_tip(pool, stop_receiver)
+            .await
+            .unwrap_err();
+        assert!(
+            err.downcast_ref::<StorageNotInitialized>().is_some(),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn migration_with_lower_bound() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let result = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 2)
+                .from_miniblock(MiniblockNumber(2))
+                .build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.miniblocks_affected, 3);
+        assert_eq!(result.miniblocks_remaining, 0);
 
+        // Miniblocks before the lower bound should not be touched.
+        for number in 0..2 {
+            assert!(
+                !is_fee_address_migrated(&mut storage, MiniblockNumber(number))
+                    .await
+                    .unwrap()
+            );
+        }
+        for number in 2..5 {
             let fee_address = storage
                 .blocks_dal()
                 .get_fee_address_for_miniblock(MiniblockNumber(number))
                 .await
-                .unwrap()
-                .expect("no fee address");
+                .unwrap();
+            assert!(
+                is_fee_address_migrated(&mut storage, MiniblockNumber(number))
+                    .await
+                    .unwrap()
+            );
             let expected_address = Address::from_low_u64_be(u64::from(number) + 1);
-            assert_eq!(fee_address, expected_address);
+            assert_eq!(fee_address, Some(expected_address));
         }
     }
 
-    #[test_casing(3, [1, 2, 3])]
     #[tokio::test]
-    async fn migration_basics(chunk_size: u32) {
-        // Replicate providing a pool with a single connection.
-        let pool = ConnectionPool::constrained_test_pool(1).await;
+    async fn resuming_migration_from_l1_batch() {
+        let pool = ConnectionPool::test_pool().await;
         let mut storage = pool.access_storage().await.unwrap();
         prepare_storage(&mut storage).await;
         drop(storage);
@@ -226,118 +2620,205 @@ mod tests {
         let (_stop_sender, stop_receiver) = watch::channel(false);
         let result = migrate_miniblocks_inner(
             pool.clone(),
-            MiniblockNumber(4),
-            chunk_size,
-            Duration::ZERO,
+            test_config(MiniblockNumber(4), 2)
+                .resume_from_batch(L1BatchNumber(2))
+                .build(),
             stop_receiver.clone(),
         )
         .await
         .unwrap();
+        // Each L1 batch contains a single miniblock with the same number.
+        assert_eq!(result.miniblocks_affected, 3);
+        assert_eq!(result.resume_from, None);
 
-        assert_eq!(result.miniblocks_affected, 5);
+        let mut storage = pool.access_storage().await.unwrap();
+        for number in 0..5 {
+            let is_migrated = is_fee_address_migrated(&mut storage, MiniblockNumber(number))
+                .await
+                .unwrap();
+            assert_eq!(is_migrated, number >= 2, "{number}");
+        }
+        drop(storage);
 
-        // Check that all blocks are migrated.
+        let err = migrate_miniblocks_inner(
+            pool.clone(),
+            test_config(MiniblockNumber(4), 2)
+                .resume_from_batch(L1BatchNumber(10))
+                .build(),
+            stop_receiver,
+        )
+        .await
+        .unwrap_err();
+        assert!(format!("{err:#}").contains("not sealed"), "{err:#}");
+    }
+
+    #[tokio::test]
+    async fn stopping_migration_on_signal() {
+        let pool = ConnectionPool::test_pool().await;
         let mut storage = pool.access_storage().await.unwrap();
-        assert_migration(&mut storage).await;
+        prepare_storage(&mut storage).await;
         drop(storage);
 
-        // Check that migration can run again w/o returning an error, hanging up etc.
+        let (signal_sender, signal_receiver) = oneshot::channel();
+        let mut stop_receiver = stop_on_signal(signal_receiver);
+        signal_sender.send(()).unwrap();
+        stop_receiver.changed().await.unwrap();
+        assert!(*stop_receiver.borrow());
+
         let result = migrate_miniblocks_inner(
             pool.clone(),
-            MiniblockNumber(4),
-            chunk_size,
-            Duration::ZERO,
+            test_config(MiniblockNumber(4), 1)
+                .backoff(BackoffPolicy::Fixed(Duration::from_secs(1_000)))
+                .build(),
             stop_receiver,
         )
         .await
         .unwrap();
+        // Migration should stop after a single chunk.
+        assert_eq!(result.miniblocks_affected, 1);
+        assert_eq!(result.resume_from, Some(MiniblockNumber(1)));
+    }
 
-        assert_eq!(result.miniblocks_affected, 0);
+    #[tokio::test]
+    async fn migration_is_not_stopped_without_signal() {
+        let (signal_sender, signal_receiver) = oneshot::channel::<()>();
+        let stop_receiver = stop_on_signal(signal_receiver);
+        drop(signal_sender);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!*stop_receiver.borrow());
+    }
+
+    #[test]
+    fn writing_and_reading_checkpoint() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("checkpoint");
+        assert_eq!(read_checkpoint(&path).unwrap(), None);
+
+        for checkpoint in [
+            Checkpoint::InProgress(MiniblockNumber(42)),
+            Checkpoint::InProgress(MiniblockNumber(100)),
+            Checkpoint::Completed,
+        ] {
+            write_checkpoint(&path, checkpoint).unwrap();
+            assert_eq!(read_checkpoint(&path).unwrap(), Some(checkpoint));
+        }
+        // The temporary file should be renamed.
+        assert!(!path.with_extension("tmp").exists());
+
+        fs::write(path.with_extension("tmp"), "4").unwrap();
+        remove_stale_checkpoint_files(&path).unwrap();
+        assert!(!path.with_extension("tmp").exists());
+        assert_eq!(read_checkpoint(&path).unwrap(), Some(Checkpoint::Completed));
+        remove_stale_checkpoint_files(&path).unwrap(); // should be idempotent
+
+        fs::write(&path, "not a number").unwrap();
+        let err = read_checkpoint(&path).unwrap_err().to_string();
+        assert!(err.contains("Malformed checkpoint"), "{err}");
     }
 
-    #[test_casing(3, [1, 2, 3])]
     #[tokio::test]
-    async fn stopping_and_resuming_migration(chunk_size: u32) {
-        let pool = ConnectionPool::constrained_test_pool(1).await;
+    async fn resuming_migration_from_checkpoint() {
+        let pool = ConnectionPool::test_pool().await;
         let mut storage = pool.access_storage().await.unwrap();
         prepare_storage(&mut storage).await;
         drop(storage);
 
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let checkpoint_path = temp_dir.path().join("checkpoint");
         let (_stop_sender, stop_receiver) = watch::channel(true); // signal stop right away
         let result = migrate_miniblocks_inner(
             pool.clone(),
-            MiniblockNumber(4),
-            chunk_size,
-            Duration::from_secs(1_000),
+            test_config(MiniblockNumber(4), 2)
+                .backoff(BackoffPolicy::Fixed(Duration::from_secs(1_000)))
+                .checkpoint_path(checkpoint_path.clone())
+                .build(),
             stop_receiver,
         )
         .await
         .unwrap();
+        assert_eq!(result.miniblocks_affected, 2);
+        assert_eq!(
+            read_checkpoint(&checkpoint_path).unwrap(),
+            Some(Checkpoint::InProgress(MiniblockNumber(2)))
+        );
 
-        // Migration should stop after a single chunk.
-        assert_eq!(result.miniblocks_affected, u64::from(chunk_size));
-
-        // Check that migration resumes from the same point.
+        // Move the checkpoint forward; the migration should skip miniblocks before it.
+        write_checkpoint(&checkpoint_path, Checkpoint::InProgress(MiniblockNumber(3))).unwrap();
         let (_stop_sender, stop_receiver) = watch::channel(false);
         let result = migrate_miniblocks_inner(
             pool.clone(),
-            MiniblockNumber(4),
-            chunk_size,
-            Duration::ZERO,
+            test_config(MiniblockNumber(4), 2)
+                .checkpoint_path(checkpoint_path.clone())
+                .build(),
             stop_receiver,
         )
         .await
         .unwrap();
+        assert_eq!(result.miniblocks_affected, 2);
+        // The migration has run to completion.
+        assert_eq!(
+            read_checkpoint(&checkpoint_path).unwrap(),
+            Some(Checkpoint::Completed)
+        );
 
-        assert_eq!(result.miniblocks_affected, 5 - u64::from(chunk_size));
         let mut storage = pool.access_storage().await.unwrap();
-        assert_migration(&mut storage).await;
+        assert!(!is_fee_address_migrated(&mut storage, MiniblockNumber(2))
+            .await
+            .unwrap());
+        assert!(is_fee_address_migrated(&mut storage, MiniblockNumber(3))
+            .await
+            .unwrap());
     }
 
-    #[test_casing(3, [1, 2, 3])]
     #[tokio::test]
-    async fn new_blocks_added_during_migration(chunk_size: u32) {
+    async fn migration_with_out_of_range_last_miniblock() {
         let pool = ConnectionPool::test_pool().await;
-        let mut storage = pool.access_storage().await.unwrap();
-        prepare_storage(&mut storage).await;
-
-        let (_stop_sender, stop_receiver) = watch::channel(true); // signal stop right away
-        let result = migrate_miniblocks_inner(
-            pool.clone(),
-            MiniblockNumber(4),
-            chunk_size,
-            Duration::from_secs(1_000),
+        let (_stop_sender, stop_receiver) = watch::channel(false);
+        let err = migrate_miniblocks_inner(
+            pool,
+            test_config(MiniblockNumber(u32::MAX), 2).build(),
             stop_receiver,
         )
         .await
-        .unwrap();
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("out of range"), "{err}");
+    }
 
-        // Migration should stop after a single chunk.
-        assert_eq!(result.miniblocks_affected, u64::from(chunk_size));
+    #[tokio::test]
+    async fn completed_checkpoint_makes_migration_noop() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        prepare_storage(&mut storage).await;
+        drop(storage);
 
-        // Insert a new miniblock to the storage with a defined fee account address.
-        let mut miniblock = create_miniblock(5);
-        miniblock.fee_account_address = Address::repeat_byte(1);
-        storage
-            .blocks_dal()
-            .insert_miniblock(&miniblock)
-            .await
-            .unwrap();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let checkpoint_path = temp_dir.path().join("checkpoint");
+        write_checkpoint(&checkpoint_path, Checkpoint::Completed).unwrap();
+        // Emulate a stale temporary file left by a terminated process.
+        fs::write(checkpoint_path.with_extension("tmp"), "2").unwrap();
 
-        // Resume the migration.
         let (_stop_sender, stop_receiver) = watch::channel(false);
         let result = migrate_miniblocks_inner(
             pool.clone(),
-            MiniblockNumber(5),
-            chunk_size,
-            Duration::ZERO,
+            test_config(MiniblockNumber(4), 2)
+                .checkpoint_path(checkpoint_path.clone())
+                .build(),
             stop_receiver,
         )
         .await
         .unwrap();
+        assert_eq!(result.miniblocks_affected, 0);
+        assert_eq!(
+            read_checkpoint(&checkpoint_path).unwrap(),
+            Some(Checkpoint::Completed)
+        );
+        assert!(!checkpoint_path.with_extension("tmp").exists());
 
-        // The new miniblock should not be affected.
-        assert_eq!(result.miniblocks_affected, 5 - u64::from(chunk_size));
-        assert_migration(&mut storage).await;
+        // The migration has been skipped, so miniblocks are not migrated.
+        let mut storage = pool.access_storage().await.unwrap();
+        assert!(!is_fee_address_migrated(&mut storage, MiniblockNumber(0))
+            .await
+            .unwrap());
     }
 }
@@ -20,6 +20,7 @@ use super::{
     updates::{MiniblockSealCommand, UpdatesManager},
 };
 
+pub(crate) mod chunked_migration;
 pub(crate) mod common;
 pub(crate) mod fee_address_migration;
 pub(crate) mod mempool;
@@ -419,8 +419,9 @@ impl MiniblockSealCommand {
         let progress = MINIBLOCK_METRICS.start(MiniblockSealStage::InsertEvents, is_fictive);
         transaction
             .events_dal()
-            .save_events(miniblock_number, &miniblock_events)
-            .await;
+            .save_events(miniblock_number, l1_batch_number, &miniblock_events)
+            .await
+            .unwrap();
         progress.observe(miniblock_event_count);
 
         let progress = MINIBLOCK_METRICS.start(MiniblockSealStage::ExtractL2ToL1Logs, is_fictive);
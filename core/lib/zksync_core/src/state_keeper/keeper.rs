@@ -83,10 +83,9 @@ impl ZkSyncStateKeeper {
         &self,
         pool: ConnectionPool,
     ) -> impl Future<Output = anyhow::Result<()>> {
-        let last_miniblock = self.io.current_miniblock_number() - 1;
         let stop_receiver = self.stop_receiver.clone();
         async move {
-            fee_address_migration::migrate_miniblocks(pool, last_miniblock, stop_receiver).await?;
+            fee_address_migration::migrate_miniblocks_to_tip(pool, stop_receiver).await?;
             future::pending::<()>().await;
             // ^ Since this is run as a task, we don't want it to exit on success (this would shut down the node).
             anyhow::Ok(())
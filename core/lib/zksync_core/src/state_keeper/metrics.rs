@@ -374,3 +374,21 @@ pub(super) struct ExecutorMetrics {
 
 #[vise::register]
 pub(super) static EXECUTOR_METRICS: vise::Global<ExecutorMetrics> = vise::Global::new();
+
+/// Progress metrics for the fee address migration.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_state_keeper_fee_address_migration")]
+pub(super) struct FeeAddressMigrationMetrics {
+    /// First miniblock of the last processed chunk.
+    pub chunk_start: Gauge<u64>,
+    /// Number of miniblocks affected by the migration so far.
+    pub miniblocks_affected: Gauge<u64>,
+    /// Estimated percentage of the migrated range that is processed.
+    pub progress_percent: Gauge<f64>,
+    /// Number of processed chunks, including chunks that were already migrated.
+    pub chunks_processed: Counter,
+}
+
+#[vise::register]
+pub(super) static FEE_ADDRESS_MIGRATION_METRICS: vise::Global<FeeAddressMigrationMetrics> =
+    vise::Global::new();
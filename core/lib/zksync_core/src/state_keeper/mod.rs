@@ -15,7 +15,14 @@ pub use self::{
     keeper::ZkSyncStateKeeper,
 };
 pub(crate) use self::{
-    mempool_actor::MempoolFetcher, seal_criteria::SequencerSealer, types::MempoolGuard,
+    io::fee_address_migration::{
+        dry_run_migration, estimate_chunk_memory, estimate_migration_cost,
+        estimate_write_amplification, migrate_miniblocks_in_txn, migrate_miniblocks_until_signal,
+        verify_migration, DryRunReport, MigrationEstimate, VerificationReport,
+    },
+    mempool_actor::MempoolFetcher,
+    seal_criteria::SequencerSealer,
+    types::MempoolGuard,
 };
 use crate::fee_model::BatchFeeModelInputProvider;
 
@@ -125,7 +125,7 @@ impl UpdaterCursor {
         // Use the snapshot L1 batch, or the genesis batch if we are not using a snapshot. Technically, the snapshot L1 batch
         // is not necessarily proven / executed yet, but since it and earlier batches are not stored, it serves
         // a natural lower boundary for the cursor.
-        let starting_l1_batch_number = L1BatchNumber(first_l1_batch_number.saturating_sub(1));
+        let starting_l1_batch_number = first_l1_batch_number.saturating_sub(1);
 
         let last_executed_l1_batch = storage
             .blocks_dal()
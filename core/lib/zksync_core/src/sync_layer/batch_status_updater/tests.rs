@@ -108,7 +108,7 @@ impl L1BatchStagesMap {
         for (number, stage) in self.iter() {
             let local_details = storage
                 .blocks_web3_dal()
-                .get_block_details(MiniblockNumber(number.0))
+                .get_block_details(MiniblockNumber::from_raw(number.into_raw()))
                 .await
                 .unwrap()
                 .unwrap_or_else(|| panic!("no details for block #{number}"));
@@ -188,7 +188,7 @@ impl MainNodeClient for MockMainNodeClient {
         Ok(map
             .get(number)
             .is_some()
-            .then_some(MiniblockNumber(number.0)))
+            .then_some(MiniblockNumber::from_raw(number.into_raw())))
     }
 
     async fn block_details(
@@ -196,7 +196,7 @@ impl MainNodeClient for MockMainNodeClient {
         number: MiniblockNumber,
     ) -> Result<Option<api::BlockDetails>, ClientError> {
         let map = self.0.lock().await;
-        let Some(stage) = map.get(L1BatchNumber(number.0)) else {
+        let Some(stage) = map.get(L1BatchNumber::from_raw(number.into_raw())) else {
             return Ok(None);
         };
         Ok(Some(mock_block_details(number.0, stage)))
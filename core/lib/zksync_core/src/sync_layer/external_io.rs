@@ -91,7 +91,7 @@ impl ExternalIO {
             cursor.next_miniblock,
         );
 
-        sync_state.set_local_block(MiniblockNumber(cursor.next_miniblock.saturating_sub(1)));
+        sync_state.set_local_block(cursor.next_miniblock.saturating_sub(1));
 
         Ok(Self {
             miniblock_sealer_handle,
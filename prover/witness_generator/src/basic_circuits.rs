@@ -501,9 +501,7 @@ async fn generate_witness(
 
     let previous_batch_with_metadata = connection
         .blocks_dal()
-        .get_l1_batch_metadata(zksync_types::L1BatchNumber(
-            input.block_number.checked_sub(1).unwrap(),
-        ))
+        .get_l1_batch_metadata(input.block_number.checked_sub(1).unwrap())
         .await
         .unwrap()
         .unwrap();